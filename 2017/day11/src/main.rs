@@ -2,12 +2,36 @@ use shared::*;
 
 const INPUT: &str = day_input!();
 
+fn parse_input() -> Vec<FlatHexDir> {
+    INPUT
+        .trim()
+        .split(',')
+        .map(|d| d.parse().unwrap())
+        .collect()
+}
+
 fn part1() {
-    println!("Part 1: {}", -1)
+    let dirs = parse_input();
+
+    let mut pos = Hex::zero();
+    for dir in dirs {
+        pos += dir;
+    }
+
+    println!("Part 1: {}", pos.length())
 }
 
 fn part2() {
-    println!("Part 2: {}", -1)
+    let dirs = parse_input();
+
+    let mut pos = Hex::zero();
+    let mut furthest = 0;
+    for dir in dirs {
+        pos += dir;
+        furthest = furthest.max(pos.length());
+    }
+
+    println!("Part 2: {}", furthest)
 }
 
 fn main() {
@@ -59,49 +59,15 @@ fn print_grid(grid: &Grid2<Cell>) {
     }
 }
 
-// Returns if anything changed
-fn move_rolling_rocks_in_dir(grid: &mut Grid2<Cell>, dir: Vec2<i32>) -> bool {
-    // Iterate over every cell, if it's a rolling rock then try to move it in the given direction
-    // if it's empty there, otherwise do nothing
-
-    let mut anything_changed = false;
-    for y in 0..grid.height() {
-        for x in 0..grid.width() {
-            let pos = Pos2::new(x as i32, y as i32);
-            let cell = grid.get(pos).unwrap();
-            if *cell != Cell::RollingRock {
-                continue;
-            }
-
-            let new_pos = pos + dir;
-            if !grid.is_in_bounds(new_pos) {
-                continue;
-            }
-
-            let new_cell = grid.get(new_pos).unwrap();
-            if *new_cell != Cell::Empty {
-                continue;
-            }
-
-            // Move the rock
-            grid[pos] = Cell::Empty;
-            grid[new_pos] = Cell::RollingRock;
-            anything_changed = true;
-        }
-    }
-
-    anything_changed
-}
-
-fn move_rocks_until_settled(grid: &mut Grid2<Cell>, dir: Vec2<i32>) {
-    while move_rolling_rocks_in_dir(grid, dir) {}
+fn move_rocks_until_settled(grid: &mut Grid2<Cell>, dir: Dir4) {
+    grid.slide_cells(dir, |c| *c == Cell::RollingRock, |c| *c == Cell::StaticRock);
 }
 
 fn part1() {
     let mut input = parse_input();
 
     // Move rocks up
-    move_rocks_until_settled(&mut input, Vec2::new(0, -1));
+    move_rocks_until_settled(&mut input, Dir4::Up);
 
     let mut sum = 0;
     for y in 0..input.height() {
@@ -127,19 +93,12 @@ fn part2() {
 
     let mut past_inputs = vec![];
 
-    let mut i = 0;
-
     // Move rocks up
     loop {
-        move_rocks_until_settled(&mut input, Vec2::new(0, -1));
-        move_rocks_until_settled(&mut input, Vec2::new(-1, 0));
-        move_rocks_until_settled(&mut input, Vec2::new(0, 1));
-        move_rocks_until_settled(&mut input, Vec2::new(1, 0));
-
-        i += 1;
-        dbg!(i);
-
-        print_grid(&input);
+        move_rocks_until_settled(&mut input, Dir4::Up);
+        move_rocks_until_settled(&mut input, Dir4::Left);
+        move_rocks_until_settled(&mut input, Dir4::Down);
+        move_rocks_until_settled(&mut input, Dir4::Right);
 
         if past_inputs.contains(&input) {
             break;
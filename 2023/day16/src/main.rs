@@ -1,7 +1,4 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    str::FromStr,
-};
+use std::{collections::VecDeque, str::FromStr};
 
 use shared::*;
 
@@ -33,24 +30,7 @@ impl Default for Cell {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-fn direction_to_vector(dir: Direction) -> Vec2<i32> {
-    match dir {
-        Direction::Up => Vec2::new(0, -1),
-        Direction::Down => Vec2::new(0, 1),
-        Direction::Left => Vec2::new(-1, 0),
-        Direction::Right => Vec2::new(1, 0),
-    }
-}
-
-fn cell_to_directions(cell: Cell, dir: Direction) -> Vec<Direction> {
+fn cell_to_directions(cell: Cell, dir: Dir4) -> Vec<Dir4> {
     // If the beam encounters empty space (.), it continues in the same direction.
     // If the beam encounters a mirror (/ or \), the beam is reflected 90 degrees depending on the angle of the mirror. For instance, a rightward-moving beam that encounters a / mirror would continue upward in the mirror's column, while a rightward-moving beam that encounters a \ mirror would continue downward from the mirror's column.
     // If the beam encounters the pointy end of a splitter (| or -), the beam passes through the splitter as if the splitter were empty space. For instance, a rightward-moving beam that encounters a - splitter would continue in the same direction.
@@ -59,32 +39,32 @@ fn cell_to_directions(cell: Cell, dir: Direction) -> Vec<Direction> {
     match (cell, dir) {
         (Cell::Empty, _) => vec![dir],
 
-        (Cell::Vertical, Direction::Up) => vec![Direction::Up],
-        (Cell::Vertical, Direction::Down) => vec![Direction::Down],
-        (Cell::Vertical, Direction::Left) => vec![Direction::Up, Direction::Down],
-        (Cell::Vertical, Direction::Right) => vec![Direction::Up, Direction::Down],
-
-        (Cell::Horizontal, Direction::Left) => vec![Direction::Left],
-        (Cell::Horizontal, Direction::Right) => vec![Direction::Right],
-        (Cell::Horizontal, Direction::Up) => vec![Direction::Left, Direction::Right],
-        (Cell::Horizontal, Direction::Down) => vec![Direction::Left, Direction::Right],
-
-        (Cell::Left, Direction::Up) => vec![Direction::Right],
-        (Cell::Left, Direction::Down) => vec![Direction::Left],
-        (Cell::Left, Direction::Left) => vec![Direction::Down],
-        (Cell::Left, Direction::Right) => vec![Direction::Up],
-
-        (Cell::Right, Direction::Up) => vec![Direction::Left],
-        (Cell::Right, Direction::Down) => vec![Direction::Right],
-        (Cell::Right, Direction::Left) => vec![Direction::Up],
-        (Cell::Right, Direction::Right) => vec![Direction::Down],
+        (Cell::Vertical, Dir4::Up) => vec![Dir4::Up],
+        (Cell::Vertical, Dir4::Down) => vec![Dir4::Down],
+        (Cell::Vertical, Dir4::Left) => vec![Dir4::Up, Dir4::Down],
+        (Cell::Vertical, Dir4::Right) => vec![Dir4::Up, Dir4::Down],
+
+        (Cell::Horizontal, Dir4::Left) => vec![Dir4::Left],
+        (Cell::Horizontal, Dir4::Right) => vec![Dir4::Right],
+        (Cell::Horizontal, Dir4::Up) => vec![Dir4::Left, Dir4::Right],
+        (Cell::Horizontal, Dir4::Down) => vec![Dir4::Left, Dir4::Right],
+
+        (Cell::Left, Dir4::Up) => vec![Dir4::Right],
+        (Cell::Left, Dir4::Down) => vec![Dir4::Left],
+        (Cell::Left, Dir4::Left) => vec![Dir4::Down],
+        (Cell::Left, Dir4::Right) => vec![Dir4::Up],
+
+        (Cell::Right, Dir4::Up) => vec![Dir4::Left],
+        (Cell::Right, Dir4::Down) => vec![Dir4::Right],
+        (Cell::Right, Dir4::Left) => vec![Dir4::Up],
+        (Cell::Right, Dir4::Right) => vec![Dir4::Down],
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 struct Beam {
     pos: Pos2<i32>,
-    dir: Direction,
+    dir: Dir4,
 }
 
 fn cell_from_char(c: char) -> Cell {
@@ -122,27 +102,22 @@ fn parse_input() -> Grid2<Cell> {
     grid
 }
 
-fn get_affected_grid(
-    grid: &Grid2<Cell>,
-    start: Pos2<i32>,
-    dir: Direction,
-) -> Grid2<HashSet<Direction>> {
+fn get_affected_grid(grid: &Grid2<Cell>, start: Pos2<i32>, dir: Dir4) -> Grid2<Dir4Set> {
     let mut beams: VecDeque<_> = [Beam { pos: start, dir }].into();
 
-    let mut affected_grid = Grid2::<HashSet<Direction>>::new_default(grid.width(), grid.height());
+    let mut affected_grid = Grid2::<Dir4Set>::new_default(grid.width(), grid.height());
 
     while let Some(beam) = beams.pop_front() {
         let affected = affected_grid.get_mut(beam.pos).unwrap();
-        if affected.contains(&beam.dir) {
+        if !affected.insert(beam.dir) {
             continue;
         }
-        affected.insert(beam.dir);
 
         let cell = grid.get(beam.pos).unwrap();
         let dirs = cell_to_directions(*cell, beam.dir);
 
         for dir in dirs {
-            let pos = beam.pos + direction_to_vector(dir);
+            let pos = beam.pos + dir.to_vec();
             if !grid.is_in_bounds(pos) {
                 continue;
             }
@@ -158,13 +133,13 @@ fn get_affected_grid(
     affected_grid
 }
 
-fn affected_grid_count(affected_grid: &Grid2<HashSet<Direction>>) -> usize {
+fn affected_grid_count(affected_grid: &Grid2<Dir4Set>) -> usize {
     let mut sum = 0;
     for y in 0..affected_grid.height() {
         for x in 0..affected_grid.width() {
             let pos = Pos2::new(x, y);
             let cell = affected_grid.get(pos).unwrap();
-            if cell.len() > 0 {
+            if !cell.is_empty() {
                 sum += 1;
             }
         }
@@ -175,7 +150,7 @@ fn affected_grid_count(affected_grid: &Grid2<HashSet<Direction>>) -> usize {
 fn part1() {
     let grid = parse_input();
 
-    let affected_grid = get_affected_grid(&grid, Pos2::new(0, 0), Direction::Right);
+    let affected_grid = get_affected_grid(&grid, Pos2::new(0, 0), Dir4::Right);
 
     let sum = affected_grid_count(&affected_grid);
 
@@ -192,11 +167,11 @@ fn part2() {
 
     // Try all possible starting positions
     for y in 0..input.height() {
-        let grid1 = get_affected_grid(&input, Pos2::new(0, y as i32), Direction::Right);
+        let grid1 = get_affected_grid(&input, Pos2::new(0, y as i32), Dir4::Right);
         let grid2 = get_affected_grid(
             &input,
             Pos2::new(input.width() as i32 - 1, y as i32),
-            Direction::Left,
+            Dir4::Left,
         );
 
         let sum1 = affected_grid_count(&grid1);
@@ -206,11 +181,11 @@ fn part2() {
     }
 
     for x in 0..input.width() {
-        let grid1 = get_affected_grid(&input, Pos2::new(x as i32, 0), Direction::Down);
+        let grid1 = get_affected_grid(&input, Pos2::new(x as i32, 0), Dir4::Down);
         let grid2 = get_affected_grid(
             &input,
             Pos2::new(x as i32, input.height() as i32 - 1),
-            Direction::Up,
+            Dir4::Up,
         );
 
         let sum1 = affected_grid_count(&grid1);
@@ -11,7 +11,7 @@ fn parse_input() -> Grid2<u32> {
 #[derive(Debug, Clone, Eq, PartialEq)]
 struct Position {
     pos: Pos2<i32>,
-    dir: Vec2<i32>,
+    dir: Dir4,
     moved_consecutive: u32,
     heat_lost: u32,
     prev_pos: Vec<Pos2<i32>>,
@@ -37,7 +37,7 @@ impl PartialOrd for PositionContainer {
 }
 
 impl Position {
-    fn new(pos: Pos2<i32>, dir: Vec2<i32>) -> Self {
+    fn new(pos: Pos2<i32>, dir: Dir4) -> Self {
         Self {
             pos,
             dir,
@@ -57,7 +57,7 @@ impl Position {
         let mut new = self.clone();
 
         new.prev_pos.push(new.pos);
-        new.pos += new.dir;
+        new.pos += new.dir.to_vec();
 
         if !input.is_in_bounds(new.pos) {
             return None;
@@ -78,8 +78,8 @@ impl Position {
         let mut new = self.clone();
 
         new.prev_pos.push(new.pos);
-        new.dir = Vec2::new(-new.dir.y, new.dir.x);
-        new.pos += new.dir;
+        new.dir = new.dir.turn_left();
+        new.pos += new.dir.to_vec();
 
         if !input.is_in_bounds(new.pos) {
             return None;
@@ -96,8 +96,8 @@ impl Position {
         let mut new = self.clone();
 
         new.prev_pos.push(new.pos);
-        new.dir = Vec2::new(new.dir.y, -new.dir.x);
-        new.pos += new.dir;
+        new.dir = new.dir.turn_right();
+        new.pos += new.dir.to_vec();
 
         if !input.is_in_bounds(new.pos) {
             return None;
@@ -129,21 +129,6 @@ fn print_grid_with_path(grid: &Grid2<u32>, min_path: &[Pos2<i32>]) {
     }
 }
 
-fn dir_to_cell(dir: Vec2<i32>) -> usize {
-    // Each of the 4 directions gets assigned an int
-    if dir == Vec2::new(0, -1) {
-        0
-    } else if dir == Vec2::new(0, 1) {
-        1
-    } else if dir == Vec2::new(-1, 0) {
-        2
-    } else if dir == Vec2::new(1, 0) {
-        3
-    } else {
-        panic!("Invalid direction: {:?}", dir);
-    }
-}
-
 fn part1() {
     let grid = parse_input();
 
@@ -153,7 +138,7 @@ fn part1() {
     let mut queue = BinaryHeap::new();
     queue.push(PositionContainer(Position::new(
         Pos2::new(0, 0),
-        Vec2::new(1, 0),
+        Dir4::Right,
     )));
 
     let winning_pos = Pos2::new(grid.width() as i32 - 1, grid.height() as i32 - 1);
@@ -181,8 +166,7 @@ fn part1() {
         }
 
         let min_cell_row = &mut min_grid_reached[position.pos];
-        let min_cell =
-            &mut min_cell_row[dir_to_cell(position.dir)][position.moved_consecutive as usize];
+        let min_cell = &mut min_cell_row[position.dir.index()][position.moved_consecutive as usize];
         if position.heat_lost >= *min_cell {
             continue;
         }
@@ -208,7 +192,7 @@ fn part1() {
 #[derive(Debug, Clone, Eq, PartialEq)]
 struct Position2 {
     pos: Pos2<i32>,
-    dir: Vec2<i32>,
+    dir: Dir4,
     moved_consecutive: u32,
     heat_lost: u32,
     prev_pos: Vec<Pos2<i32>>,
@@ -234,7 +218,7 @@ impl PartialOrd for Position2Container {
 }
 
 impl Position2 {
-    fn new(pos: Pos2<i32>, dir: Vec2<i32>) -> Self {
+    fn new(pos: Pos2<i32>, dir: Dir4) -> Self {
         Self {
             pos,
             dir,
@@ -258,7 +242,7 @@ impl Position2 {
         }
 
         new.prev_pos.push(new.pos);
-        new.pos += new.dir;
+        new.pos += new.dir.to_vec();
 
         if !input.is_in_bounds(new.pos) {
             return None;
@@ -279,8 +263,8 @@ impl Position2 {
         }
 
         new.prev_pos.push(new.pos);
-        new.dir = Vec2::new(-new.dir.y, new.dir.x);
-        new.pos += new.dir;
+        new.dir = new.dir.turn_left();
+        new.pos += new.dir.to_vec();
 
         if !input.is_in_bounds(new.pos) {
             return None;
@@ -301,8 +285,8 @@ impl Position2 {
         }
 
         new.prev_pos.push(new.pos);
-        new.dir = Vec2::new(new.dir.y, -new.dir.x);
-        new.pos += new.dir;
+        new.dir = new.dir.turn_right();
+        new.pos += new.dir.to_vec();
 
         if !input.is_in_bounds(new.pos) {
             return None;
@@ -337,11 +321,11 @@ fn part2() {
     let mut queue = BinaryHeap::new();
     queue.push(Position2Container(Position2::new(
         Pos2::new(0, 0),
-        Vec2::new(1, 0),
+        Dir4::Right,
     )));
     queue.push(Position2Container(Position2::new(
         Pos2::new(0, 0),
-        Vec2::new(0, 1),
+        Dir4::Down,
     )));
 
     let winning_pos = Pos2::new(grid.width() as i32 - 1, grid.height() as i32 - 1);
@@ -372,8 +356,7 @@ fn part2() {
         }
 
         let min_cell_row = &mut min_grid_reached[position.pos];
-        let min_cell =
-            &mut min_cell_row[dir_to_cell(position.dir)][position.moved_consecutive as usize];
+        let min_cell = &mut min_cell_row[position.dir.index()][position.moved_consecutive as usize];
         if position.heat_lost >= *min_cell {
             continue;
         }
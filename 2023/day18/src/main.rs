@@ -19,40 +19,12 @@ const INPUT: &str = day_input!();
 // L 2 (#015232)
 // U 2 (#7a21e3)
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Dir {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Dir {
-    fn clockwise(self) -> Self {
-        match self {
-            Dir::Up => Dir::Right,
-            Dir::Right => Dir::Down,
-            Dir::Down => Dir::Left,
-            Dir::Left => Dir::Up,
-        }
-    }
-}
-
-fn dir_to_vec(dir: Dir) -> Vec2<i32> {
-    match dir {
-        Dir::Up => Vec2::new(0, -1),
-        Dir::Down => Vec2::new(0, 1),
-        Dir::Left => Vec2::new(-1, 0),
-        Dir::Right => Vec2::new(1, 0),
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Move {
-    dir: Dir,
+    dir: Dir4,
     steps: usize,
 
-    dir2: Dir,
+    dir2: Dir4,
     steps2: u32,
 }
 
@@ -60,23 +32,17 @@ fn parse_lines(line: &str) -> Vec<Move> {
     line.lines()
         .map(|s| {
             let mut parts = s.split_whitespace();
-            let dir = match parts.next().unwrap() {
-                "R" => Dir::Right,
-                "L" => Dir::Left,
-                "U" => Dir::Up,
-                "D" => Dir::Down,
-                _ => unreachable!(),
-            };
+            let dir = parts.next().unwrap().parse().unwrap();
 
             let steps = parts.next().unwrap().parse().unwrap();
 
             let color_str = parts.next().unwrap();
 
             let dir2 = match color_str.chars().nth(7).unwrap() {
-                '0' => Dir::Right,
-                '1' => Dir::Down,
-                '2' => Dir::Left,
-                '3' => Dir::Up,
+                '0' => Dir4::Right,
+                '1' => Dir4::Down,
+                '2' => Dir4::Left,
+                '3' => Dir4::Up,
                 _ => unreachable!(),
             };
 
@@ -104,7 +70,7 @@ fn part1() {
 
     // Follow and trace paths
     for m in input {
-        let dir = dir_to_vec(m.dir);
+        let dir = m.dir.to_vec();
         for _ in 0..m.steps {
             pos += dir;
             affected[pos] = true;
@@ -122,8 +88,8 @@ fn part1() {
 
     // Floodfill
     while let Some(pos) = queue.pop_front() {
-        for dir in &[Dir::Up, Dir::Down, Dir::Left, Dir::Right] {
-            let new_pos = pos + dir_to_vec(*dir);
+        for dir in &[Dir4::Up, Dir4::Down, Dir4::Left, Dir4::Right] {
+            let new_pos = pos + dir.to_vec();
             if !affected[new_pos] {
                 affected[new_pos] = true;
                 queue.push_back(new_pos);
@@ -159,123 +125,11 @@ fn part1() {
 fn part2() {
     let input = parse_lines(INPUT);
 
-    // let mut pos = Pos2::new(0, 0);
-
-    // let mut row_dirs: BTreeMap<i64, BTreeMap<i64, Dir>> = BTreeMap::new();
-    // let mut add_pos_dir = |pos: Pos2<i32>, dir: Dir| {
-    //     let row = row_dirs.entry(pos.y as i64).or_default();
-    //     row.insert(pos.x as i64, dir);
-    // };
-
-    // // Follow and trace paths
-    // for m in input {
-    //     let dir = dir_to_vec(m.dir);
-
-    //     let add_dirs = m.dir == Dir::Up || m.dir == Dir::Down;
-    //     if add_dirs {
-    //         add_pos_dir(pos, m.dir);
-    //     }
-    //     for _ in 0..m.steps {
-    //         pos += dir;
-
-    //         if add_dirs {
-    //             add_pos_dir(pos, m.dir);
-    //         }
-    //     }
-    // }
-
-    // let mut sum = 0;
-    // // Count distances between up and down lines on each row
-    // for (y, row) in row_dirs.iter() {
-    //     let mut groups = Vec::new();
-    //     // Group by directions
-    //     let mut x_group = Vec::new();
-    //     let mut group_dir = None;
-    //     for (x, dir) in row.iter() {
-    //         if group_dir.is_none() {
-    //             group_dir = Some(*dir);
-    //         }
-
-    //         if *dir != group_dir.unwrap() {
-    //             groups.push((group_dir.unwrap(), x_group));
-    //             x_group = Vec::new();
-    //             group_dir = Some(*dir);
-    //         }
-
-    //         x_group.push(*x);
-    //     }
-    //     groups.push((group_dir.unwrap(), x_group));
-
-    //     let mut inside = false;
-    //     let mut prev_x = 0;
-    //     let mut sum2 = 0;
-    //     for (_, xs) in groups.iter() {
-    //         if !inside {
-    //             inside = true;
-    //             prev_x = *xs.first().unwrap();
-    //         } else {
-    //             inside = false;
-    //             sum2 += (xs.last().unwrap() - prev_x) + 1;
-    //         }
-    //     }
-
-    //     sum += sum2;
-    // }
-
-    // Do gauss area formula
-    let mut area_sum = 0i64;
-    let mut prev_pos = Pos2::new(0, 0);
-
-    for i in 0..input.len() {
-        let m = &input[i];
-        let m_next = &input[(i + 1) % input.len()];
-        let m_prev = if i == 0 {
-            &input[input.len() - 1]
-        } else {
-            &input[i - 1]
-        };
-
-        let dir = dir_to_vec(m.dir2);
-
-        let mut pos = prev_pos;
-
-        if m_prev.dir2.clockwise() == m.dir2 {
-            // println!("Adding first");
-            if m.dir2 == Dir::Up {
-                area_sum -= pos.x as i64;
-            } else if m.dir2 == Dir::Down {
-                area_sum += pos.x as i64 + 1;
-            }
-        }
-
-        for _ in 0..m.steps2 - 1 {
-            pos += dir;
-
-            if m.dir2 == Dir::Up {
-                area_sum -= pos.x as i64;
-            } else if m.dir2 == Dir::Down {
-                area_sum += pos.x as i64 + 1;
-            }
-        }
-        pos += dir;
-
-        if m.dir2.clockwise() == m_next.dir2 {
-            // println!("Adding last");
-            if m.dir2 == Dir::Up {
-                area_sum -= pos.x as i64;
-            } else if m.dir2 == Dir::Down {
-                area_sum += pos.x as i64 + 1;
-            }
-        }
-
-        // dbg!(pos.x);
-        // dbg!(&m);
-        // dbg!(area_sum);
-
-        prev_pos = pos;
-    }
+    // The trench is one tile wide, so the lagoon is every lattice point on or inside the loop
+    let moves = input.iter().map(|m| (m.dir2.to_vec(), m.steps2 as i64));
+    let lagoon = Polygon::from_moves(Pos2::new(0, 0), moves);
 
-    println!("Part 2: {}", area_sum)
+    println!("Part 2: {}", lagoon.enclosed_points())
 }
 
 fn main() {
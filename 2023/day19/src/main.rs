@@ -1,4 +1,4 @@
-use std::{collections::VecDeque, ops::Range};
+use std::collections::VecDeque;
 
 use shared::*;
 
@@ -242,14 +242,14 @@ fn part1() {
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct PartRanges {
-    x_range: Range<i32>,
-    m_range: Range<i32>,
-    a_range: Range<i32>,
-    s_range: Range<i32>,
+    x_range: IntervalSet<i32>,
+    m_range: IntervalSet<i32>,
+    a_range: IntervalSet<i32>,
+    s_range: IntervalSet<i32>,
 }
 
 impl PartRanges {
-    fn get_field_mut(&mut self, property: Property) -> &mut Range<i32> {
+    fn get_field_mut(&mut self, property: Property) -> &mut IntervalSet<i32> {
         match property {
             Property::X => &mut self.x_range,
             Property::M => &mut self.m_range,
@@ -258,7 +258,7 @@ impl PartRanges {
         }
     }
 
-    fn get_field(&self, property: Property) -> &Range<i32> {
+    fn get_field(&self, property: Property) -> &IntervalSet<i32> {
         match property {
             Property::X => &self.x_range,
             Property::M => &self.m_range,
@@ -269,50 +269,35 @@ impl PartRanges {
 
     fn product(&self) -> i64 {
         let mut product = 1;
-        product *= self.x_range.end as i64 - self.x_range.start as i64;
-        product *= self.m_range.end as i64 - self.m_range.start as i64;
-        product *= self.a_range.end as i64 - self.a_range.start as i64;
-        product *= self.s_range.end as i64 - self.s_range.start as i64;
+        product *= self.x_range.total_len() as i64;
+        product *= self.m_range.total_len() as i64;
+        product *= self.a_range.total_len() as i64;
+        product *= self.s_range.total_len() as i64;
         product
     }
 }
 
 // Returns: (accepted, rejected)
 fn slice_range_by(ranges: &PartRanges, by: &RulePart) -> (Option<PartRanges>, Option<PartRanges>) {
-    let mut accepted_cloned = ranges.clone();
-    let mut rejected_cloned = ranges.clone();
+    let matching = match by.op {
+        RuleOp::Lt => IntervalSet::from_range(i32::MIN..by.value),
+        RuleOp::Gt => IntervalSet::from_range(by.value + 1..=i32::MAX),
+        RuleOp::Always => IntervalSet::new().complement(),
+    };
 
-    let accepted_field = accepted_cloned.get_field_mut(by.property);
-    let rejected_field = rejected_cloned.get_field_mut(by.property);
     let field = ranges.get_field(by.property);
+    let with_field = |values: IntervalSet<i32>| {
+        (!values.is_empty()).then(|| {
+            let mut ranges = ranges.clone();
+            *ranges.get_field_mut(by.property) = values;
+            ranges
+        })
+    };
 
-    let by_val = by.value;
-
-    match by.op {
-        RuleOp::Lt => {
-            if field.start >= by_val {
-                (None, Some(rejected_cloned))
-            } else if field.end < by_val {
-                (Some(accepted_cloned), None)
-            } else {
-                accepted_field.end = by_val;
-                rejected_field.start = by_val;
-                (Some(accepted_cloned), Some(rejected_cloned))
-            }
-        }
-        RuleOp::Gt => {
-            if field.end <= by_val + 1 {
-                (None, Some(rejected_cloned))
-            } else if field.start > by_val {
-                (Some(accepted_cloned), None)
-            } else {
-                accepted_field.start = by_val + 1;
-                rejected_field.end = by_val + 1;
-                (Some(accepted_cloned), Some(rejected_cloned))
-            }
-        }
-        RuleOp::Always => (Some(accepted_cloned), None),
-    }
+    (
+        with_field(field.intersection(&matching)),
+        with_field(field.difference(&matching)),
+    )
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -324,11 +309,12 @@ struct SearchNode {
 fn part2() {
     let input = parse_input();
 
+    let rating = IntervalSet::from_range(1..4001);
     let starting_ranges = PartRanges {
-        x_range: 1..4001,
-        m_range: 1..4001,
-        a_range: 1..4001,
-        s_range: 1..4001,
+        x_range: rating.clone(),
+        m_range: rating.clone(),
+        a_range: rating.clone(),
+        s_range: rating,
     };
 
     let start_node = SearchNode {
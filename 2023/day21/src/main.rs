@@ -1,7 +1,4 @@
-use std::{
-    collections::{HashSet, VecDeque},
-    str::FromStr,
-};
+use std::{collections::VecDeque, str::FromStr};
 
 use shared::*;
 
@@ -21,9 +18,10 @@ const INPUT: &str = day_input!();
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Cell {
-    Empty, // .
-    Bush,  // #
-    Start, // S
+    Empty,   // .
+    Bush,    // #
+    Start,   // S
+    Reached, // O
 }
 
 impl FromStr for Cell {
@@ -45,39 +43,28 @@ fn parse_input() -> Grid2<Cell> {
 
 fn part1() {
     let mut input = parse_input();
-    // Find Start and replace it with Empty
-    let mut start = Pos2::new(0, 0);
-    for x in 0..input.width() {
-        for y in 0..input.height() {
+    // The start counts as reached at step 0
+    for y in 0..input.height() {
+        for x in 0..input.width() {
             if input[Pos2::new(x, y)] == Cell::Start {
-                start = Pos2::new(x as i32, y as i32);
-                input[Pos2::new(x, y)] = Cell::Empty;
+                input[Pos2::new(x, y)] = Cell::Reached;
             }
         }
     }
 
-    let target_step_count = 64;
-    let mut positions_at_step_count = HashSet::from([start]);
+    // A plot is reached after a step exactly when a neighbor was reached before it
+    let mut automaton = Automaton::new(input, Neighborhood::Four, |cell| match cell.value {
+        Cell::Bush => Cell::Bush,
+        _ if cell.any_neighbor(|&n| n == Cell::Reached) => Cell::Reached,
+        _ => Cell::Empty,
+    });
+    automaton.run(64);
 
-    for _ in 0..target_step_count {
-        let mut new_positions = HashSet::new();
-
-        for pos in &positions_at_step_count {
-            for new_pos in pos.iter_four_directions() {
-                if !input.is_in_bounds(new_pos) {
-                    continue;
-                }
-
-                if input[new_pos] == Cell::Empty {
-                    new_positions.insert(new_pos);
-                }
-            }
-        }
-
-        positions_at_step_count = new_positions;
-    }
-
-    let result = positions_at_step_count.len();
+    let grid = automaton.grid();
+    let result = (0..grid.height())
+        .flat_map(|y| (0..grid.width()).map(move |x| Pos2::new(x, y)))
+        .filter(|&pos| grid[pos] == Cell::Reached)
+        .count();
 
     println!("Part 1: {}", result)
 }
@@ -130,7 +117,7 @@ fn print_astar_grid(astar_map: &Grid2<u32>) {
 }
 
 fn sort_seeds_by_to_normalize(seeds: &mut Vec<Pos2<i32>>) {
-    seeds.sort_by_key(|pos| (pos.x, pos.y));
+    seeds.sort();
 }
 
 fn mirror_edge_coord(pos: Pos2<i32>, grid: &Grid2<Cell>) -> Vec<Pos2<i32>> {
@@ -10,51 +10,7 @@ const INPUT: &str = day_input!();
 // 0,1,6~2,1,6
 // 1,1,8~1,1,9
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Vec3d {
-    x: i32,
-    y: i32,
-    z: i32,
-}
-
-impl Vec3d {
-    pub fn new(x: i32, y: i32, z: i32) -> Self {
-        Self { x, y, z }
-    }
-
-    pub fn min(&self, other: &Self) -> Self {
-        Self {
-            x: self.x.min(other.x),
-            y: self.y.min(other.y),
-            z: self.z.min(other.z),
-        }
-    }
-
-    pub fn max(&self, other: &Self) -> Self {
-        Self {
-            x: self.x.max(other.x),
-            y: self.y.max(other.y),
-            z: self.z.max(other.z),
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-struct Cube3d {
-    pos1: Vec3d,
-    pos2: Vec3d,
-}
-
-impl Cube3d {
-    pub fn new(pos1: Vec3d, pos2: Vec3d) -> Self {
-        Self {
-            pos1: pos1.min(&pos2),
-            pos2: pos1.max(&pos2),
-        }
-    }
-}
-
-fn parse_brick_line(line: &str) -> Cube3d {
+fn parse_brick_line(line: &str) -> Box3<i32> {
     let mut parts = line.split('~');
     let pos1 = parts.next().unwrap();
     let pos2 = parts.next().unwrap();
@@ -66,49 +22,27 @@ fn parse_brick_line(line: &str) -> Cube3d {
     let x2 = pos2.next().unwrap().parse::<i32>().unwrap();
     let y2 = pos2.next().unwrap().parse::<i32>().unwrap();
     let z2 = pos2.next().unwrap().parse::<i32>().unwrap();
-    Cube3d::new(Vec3d::new(x1, y1, z1), Vec3d::new(x2, y2, z2))
+    Box3::from_inclusive(Pos3::new(x1, y1, z1), Pos3::new(x2, y2, z2))
 }
 
-fn parse_input() -> Vec<Cube3d> {
+fn parse_input() -> Vec<Box3<i32>> {
     INPUT
         .lines()
         .map(|line| parse_brick_line(line))
         .collect::<Vec<_>>()
 }
 
-fn offset_brick_by(brick: &Cube3d, offset: &Vec3d) -> Cube3d {
-    Cube3d {
-        pos1: Vec3d {
-            x: brick.pos1.x + offset.x,
-            y: brick.pos1.y + offset.y,
-            z: brick.pos1.z + offset.z,
-        },
-        pos2: Vec3d {
-            x: brick.pos2.x + offset.x,
-            y: brick.pos2.y + offset.y,
-            z: brick.pos2.z + offset.z,
-        },
-    }
-}
-
-fn do_bricks_overlap(brick1: &Cube3d, brick2: &Cube3d) -> bool {
-    let x_overlap = brick1.pos1.x <= brick2.pos2.x && brick1.pos2.x >= brick2.pos1.x;
-    let y_overlap = brick1.pos1.y <= brick2.pos2.y && brick1.pos2.y >= brick2.pos1.y;
-    let z_overlap = brick1.pos1.z <= brick2.pos2.z && brick1.pos2.z >= brick2.pos1.z;
-    x_overlap && y_overlap && z_overlap
-}
-
-fn is_brick_negative(brick: &Cube3d) -> bool {
-    brick.pos1.z < 0
+fn is_brick_negative(brick: &Box3<i32>) -> bool {
+    brick.min().z < 0
 }
 
-fn settle_bricks(bricks: &mut Vec<Cube3d>) {
+fn settle_bricks(bricks: &mut Vec<Box3<i32>>) {
     let mut settled_bricks = 0;
     while settled_bricks < bricks.len() {
         let mut curr_settled = 0;
         let mut new_bricks = Vec::new();
         for (i, b) in bricks.iter().enumerate() {
-            let b_shifted = offset_brick_by(b, &Vec3d::new(0, 0, -1));
+            let b_shifted = b.translate(Vec3::new(0, 0, -1));
 
             if is_brick_negative(&b_shifted) {
                 curr_settled += 1;
@@ -122,7 +56,7 @@ fn settle_bricks(bricks: &mut Vec<Cube3d>) {
                 if i == j {
                     continue;
                 }
-                if do_bricks_overlap(&b_shifted, b2) {
+                if b_shifted.intersects(b2) {
                     overlaps = true;
                     break;
                 }
@@ -141,9 +75,9 @@ fn settle_bricks(bricks: &mut Vec<Cube3d>) {
     }
 }
 
-fn are_bricks_unsettled(bricks: &Vec<Cube3d>) -> bool {
+fn are_bricks_unsettled(bricks: &Vec<Box3<i32>>) -> bool {
     for (i, b) in bricks.iter().enumerate() {
-        let b_shifted = offset_brick_by(b, &Vec3d::new(0, 0, -1));
+        let b_shifted = b.translate(Vec3::new(0, 0, -1));
 
         if is_brick_negative(&b_shifted) {
             continue;
@@ -155,7 +89,7 @@ fn are_bricks_unsettled(bricks: &Vec<Cube3d>) -> bool {
             if i == j {
                 continue;
             }
-            if do_bricks_overlap(&b_shifted, b2) {
+            if b_shifted.intersects(b2) {
                 overlaps = true;
                 break;
             } else {
@@ -99,34 +99,9 @@ fn parse_input2() -> Race {
 fn part2() {
     let input = parse_input2();
 
-    let a = -1f64;
-    let b = input.time as f64;
-    let c = -(input.distance as f64);
-
-    let left = (-b + (b.powi(2) - 4f64 * a * c).sqrt()) / (2f64 * a);
-    let right = (-b - (b.powi(2) - 4f64 * a * c).sqrt()) / (2f64 * a);
-
-    let left = left as u64;
-    let right = right as u64;
-
-    let tries_left = (left - 2)..=(left + 2);
-    let tries_right = (right - 2)..=(right + 2);
-
-    let mut min_success = u64::MAX;
-    for i in tries_left {
-        if is_winning_condition(input, i) {
-            min_success = min_success.min(i);
-        }
-    }
-
-    let mut max_success = 0;
-    for i in tries_right {
-        if is_winning_condition(input, i) {
-            max_success = max_success.max(i);
-        }
-    }
-
-    let range = max_success - min_success + 1;
+    // Holding for x ms wins when x * (time - x) > distance
+    let race = Quadratic::new(-1, input.time as i128, -(input.distance as i128));
+    let range = race.solve(Inequality::Greater).count().unwrap();
 
     println!("Part 2: {}", range);
 }
@@ -136,70 +136,21 @@ fn find_cycle(
     unreachable!();
 }
 
-fn find_multiples(cycle_start: u64, cycle_bools: &[bool]) -> Vec<Period> {
+fn find_multiples(cycle_start: u64, cycle_bools: &[bool]) -> Vec<Periodic> {
     let mut result = Vec::new();
 
     for (i, &ends_with_z) in cycle_bools.iter().enumerate() {
         if ends_with_z {
-            result.push(Period {
-                start_offset: cycle_start + i as u64 + 1,
-                stride: cycle_bools.len() as u64,
-            });
+            result.push(Periodic::new(
+                cycle_start + i as u64 + 1,
+                cycle_bools.len() as u64,
+            ));
         }
     }
 
     result
 }
 
-#[derive(Debug, Clone, Copy)]
-struct Period {
-    start_offset: u64,
-    stride: u64,
-}
-
-fn find_overlap_time(periods: &[Period]) -> Option<u64> {
-    if periods.is_empty() {
-        return None;
-    }
-
-    let max_stride = periods.iter().map(|p| p.stride).max().unwrap_or(0);
-
-    // The time must be at least the maximum of start_offsets.
-    let mut time = periods.iter().map(|p| p.start_offset).max().unwrap_or(0);
-
-    // Keep checking until we reach the least common multiple of all strides.
-    while time <= lcm_of_all_strides(periods) {
-        if periods
-            .iter()
-            .all(|p| (time >= p.start_offset) && ((time - p.start_offset) % p.stride == 0))
-        {
-            return Some(time);
-        }
-        time += max_stride;
-    }
-
-    None
-}
-
-fn lcm_of_all_strides(periods: &[Period]) -> u64 {
-    periods
-        .iter()
-        .map(|p| p.stride)
-        .fold(1, |acc, stride| lcm(acc, stride))
-}
-
-fn gcd(a: u64, b: u64) -> u64 {
-    if b == 0 {
-        a
-    } else {
-        gcd(b, a % b)
-    }
-}
-
-fn lcm(a: u64, b: u64) -> u64 {
-    a / gcd(a, b) * b
-}
-
 // Every permutation with one element from each set
 fn permutations<T: Copy>(numbers: Vec<Vec<T>>) -> Vec<Vec<T>> {
     let mut result = Vec::<Vec<T>>::new();
@@ -250,7 +201,7 @@ fn part2() {
     let mut min_multiple = u64::MAX;
 
     for permutation in permutations {
-        let overlap_time = find_overlap_time(&permutation);
+        let overlap_time = Periodic::first_common(&permutation);
         if let Some(overlap_time) = overlap_time {
             if overlap_time < min_multiple {
                 min_multiple = overlap_time;
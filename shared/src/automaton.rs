@@ -0,0 +1,365 @@
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+};
+
+use crate::{Grid2, IGrid2, Pos2, Vec2};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Neighborhood {
+    Four,
+    Eight,
+    Custom(Vec<Vec2<isize>>),
+}
+
+impl Neighborhood {
+    pub fn offsets(&self) -> Vec<Vec2<isize>> {
+        match self {
+            Neighborhood::Four => Vec2::four_directions().to_vec(),
+            Neighborhood::Eight => Vec2::eight_directions().to_vec(),
+            Neighborhood::Custom(offsets) => offsets.clone(),
+        }
+    }
+}
+
+pub struct CellView<'a, T> {
+    pub pos: Pos2<isize>,
+    pub value: &'a T,
+    // Neighbors outside of a bounded grid are left out
+    pub neighbors: &'a [&'a T],
+}
+
+impl<'a, T> CellView<'a, T> {
+    pub fn count_neighbors(&self, f: impl Fn(&T) -> bool) -> usize {
+        self.neighbors.iter().filter(|n| f(n)).count()
+    }
+
+    pub fn any_neighbor(&self, f: impl Fn(&T) -> bool) -> bool {
+        self.neighbors.iter().any(|n| f(n))
+    }
+}
+
+pub trait AutomatonGrid: Clone {
+    type Cell: Clone + PartialEq;
+
+    // Writes the next generation into `next`, overwriting whatever it held before
+    fn step_into<F>(&self, next: &mut Self, offsets: &[Vec2<isize>], rule: &mut F)
+    where
+        F: FnMut(CellView<'_, Self::Cell>) -> Self::Cell;
+
+    fn same_cells(&self, other: &Self) -> bool;
+
+    fn hash_cells<H: Hasher>(&self, state: &mut H)
+    where
+        Self::Cell: Hash;
+}
+
+impl<T: Clone + PartialEq> AutomatonGrid for Grid2<T> {
+    type Cell = T;
+
+    fn step_into<F>(&self, next: &mut Self, offsets: &[Vec2<isize>], rule: &mut F)
+    where
+        F: FnMut(CellView<'_, T>) -> T,
+    {
+        if next.width() != self.width() || next.height() != self.height() {
+            *next = self.clone();
+        }
+
+        let mut neighbors = Vec::with_capacity(offsets.len());
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                let pos = Pos2::new(x as isize, y as isize);
+
+                neighbors.clear();
+                neighbors.extend(offsets.iter().filter_map(|&offset| self.get(pos + offset)));
+
+                next[pos] = rule(CellView {
+                    pos,
+                    value: &self[pos],
+                    neighbors: &neighbors,
+                });
+            }
+        }
+    }
+
+    fn same_cells(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    fn hash_cells<H: Hasher>(&self, state: &mut H)
+    where
+        T: Hash,
+    {
+        self.width().hash(state);
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                self[Pos2::new(x, y)].hash(state);
+            }
+        }
+    }
+}
+
+// The allocated region of an infinite grid, as inclusive min and exclusive max corners
+fn igrid_region<T: Clone>(grid: &IGrid2<T>) -> (Pos2<isize>, Pos2<isize>) {
+    (
+        Pos2::new(
+            -(grid.left_capacity() as isize),
+            -(grid.up_capacity() as isize),
+        ),
+        Pos2::new(
+            grid.right_capacity() as isize,
+            grid.down_capacity() as isize,
+        ),
+    )
+}
+
+fn iter_region(min: Pos2<isize>, max: Pos2<isize>) -> impl Iterator<Item = Pos2<isize>> {
+    (min.y..max.y).flat_map(move |y| (min.x..max.x).map(move |x| Pos2::new(x, y)))
+}
+
+// Infinite grids assume the rule keeps a default cell surrounded by default cells as default,
+// so only the allocated region plus the neighborhood reach gets evaluated
+impl<T: Clone + PartialEq> AutomatonGrid for IGrid2<T> {
+    type Cell = T;
+
+    fn step_into<F>(&self, next: &mut Self, offsets: &[Vec2<isize>], rule: &mut F)
+    where
+        F: FnMut(CellView<'_, T>) -> T,
+    {
+        let reach = offsets
+            .iter()
+            .map(|o| o.x.abs().max(o.y.abs()))
+            .max()
+            .unwrap_or(0);
+
+        // Cover the region of `next` too, so that stale cells get overwritten
+        let (self_min, self_max) = igrid_region(self);
+        let (next_min, next_max) = igrid_region(next);
+        let min = Pos2::new(self_min.x.min(next_min.x), self_min.y.min(next_min.y))
+            - Vec2::new(reach, reach);
+        let max = Pos2::new(self_max.x.max(next_max.x), self_max.y.max(next_max.y))
+            + Vec2::new(reach, reach);
+
+        let mut neighbors = Vec::with_capacity(offsets.len());
+        for pos in iter_region(min, max) {
+            neighbors.clear();
+            neighbors.extend(offsets.iter().map(|&offset| self.get(pos + offset)));
+
+            let value = rule(CellView {
+                pos,
+                value: self.get(pos),
+                neighbors: &neighbors,
+            });
+
+            // Default cells never grow the grid or its bounds
+            next.set(pos, value);
+        }
+    }
+
+    fn same_cells(&self, other: &Self) -> bool {
+        let (self_min, self_max) = igrid_region(self);
+        let (other_min, other_max) = igrid_region(other);
+        let min = Pos2::new(self_min.x.min(other_min.x), self_min.y.min(other_min.y));
+        let max = Pos2::new(self_max.x.max(other_max.x), self_max.y.max(other_max.y));
+
+        self.default_value() == other.default_value()
+            && iter_region(min, max).all(|pos| self.get(pos) == other.get(pos))
+    }
+
+    fn hash_cells<H: Hasher>(&self, state: &mut H)
+    where
+        T: Hash,
+    {
+        // Only non-default cells are hashed, so the allocated size doesn't matter
+        let (min, max) = igrid_region(self);
+        for pos in iter_region(min, max) {
+            let value = self.get(pos);
+            if value != self.default_value() {
+                pos.hash(state);
+                value.hash(state);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Repeat {
+    pub first_seen: usize,
+    pub repeated_at: usize,
+}
+
+impl Repeat {
+    pub fn period(&self) -> usize {
+        self.repeated_at - self.first_seen
+    }
+
+    // The earlier generation that has the same state as the given generation
+    pub fn equivalent_generation(&self, generation: usize) -> usize {
+        if generation < self.first_seen {
+            generation
+        } else {
+            self.first_seen + (generation - self.first_seen) % self.period()
+        }
+    }
+}
+
+pub struct Automaton<G: AutomatonGrid, F> {
+    current: G,
+    next: G,
+    offsets: Vec<Vec2<isize>>,
+    rule: F,
+    generation: usize,
+}
+
+impl<G, F> Automaton<G, F>
+where
+    G: AutomatonGrid,
+    F: FnMut(CellView<'_, G::Cell>) -> G::Cell,
+{
+    pub fn new(grid: G, neighborhood: Neighborhood, rule: F) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            offsets: neighborhood.offsets(),
+            rule,
+            generation: 0,
+        }
+    }
+
+    pub fn grid(&self) -> &G {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> G {
+        self.current
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    // Returns whether any cell changed
+    pub fn step(&mut self) -> bool {
+        self.current
+            .step_into(&mut self.next, &self.offsets, &mut self.rule);
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+
+        !self.current.same_cells(&self.next)
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    // Returns the first generation that stepped into itself, or None if the grid was still
+    // changing after max_steps
+    pub fn run_until_stable(&mut self, max_steps: usize) -> Option<usize> {
+        for _ in 0..max_steps {
+            if !self.step() {
+                return Some(self.generation - 1);
+            }
+        }
+
+        None
+    }
+
+    pub fn run_until_repeat(&mut self, max_steps: usize) -> Option<Repeat>
+    where
+        G::Cell: Hash,
+    {
+        let hash_grid = |grid: &G| {
+            let mut hasher = DefaultHasher::new();
+            grid.hash_cells(&mut hasher);
+            hasher.finish()
+        };
+
+        let mut history = vec![(self.generation, self.current.clone())];
+        let mut seen = HashMap::from([(hash_grid(&self.current), vec![0])]);
+
+        for _ in 0..max_steps {
+            self.step();
+
+            let hash = hash_grid(&self.current);
+            let entries = seen.entry(hash).or_default();
+            for &i in entries.iter() {
+                let (generation, grid) = &history[i];
+                if grid.same_cells(&self.current) {
+                    return Some(Repeat {
+                        first_seen: *generation,
+                        repeated_at: self.generation,
+                    });
+                }
+            }
+
+            entries.push(history.len());
+            history.push((self.generation, self.current.clone()));
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blinker() {
+        let mut grid = IGrid2::new_with(false);
+        for x in -1..=1 {
+            grid[Pos2::new(x, 0)] = true;
+        }
+
+        let mut automaton = Automaton::new(grid, Neighborhood::Eight, |cell| {
+            let alive = cell.count_neighbors(|&n| n);
+            alive == 3 || (*cell.value && alive == 2)
+        });
+
+        assert!(automaton.step());
+        assert!(automaton.grid()[Pos2::new(0, -1)]);
+        assert!(automaton.grid()[Pos2::new(0, 1)]);
+        assert!(!automaton.grid()[Pos2::new(-1, 0)]);
+
+        let repeat = automaton.run_until_repeat(10).unwrap();
+        assert_eq!(repeat.period(), 2);
+        assert_eq!(repeat.equivalent_generation(1000), 2);
+    }
+
+    #[test]
+    fn test_still_life_keeps_bounds() {
+        let mut grid = IGrid2::new_with(false);
+        for pos in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+            grid[Pos2::new(pos.0, pos.1)] = true;
+        }
+        let bounds = grid.bounds();
+
+        let mut automaton = Automaton::new(grid, Neighborhood::Eight, |cell| {
+            let alive = cell.count_neighbors(|&n| n);
+            alive == 3 || (*cell.value && alive == 2)
+        });
+        for _ in 0..5 {
+            automaton.step();
+            assert_eq!(automaton.grid().bounds(), bounds);
+        }
+        assert_eq!(automaton.run_until_stable(1), Some(5));
+    }
+
+    #[test]
+    fn test_flood_until_stable() {
+        let grid = Grid2::from_rows(["#..", ".#.", "..."].iter().map(|l| l.chars())).unwrap();
+
+        let mut automaton = Automaton::new(grid, Neighborhood::Four, |cell| {
+            if *cell.value == '.' && cell.any_neighbor(|&n| n == '#') {
+                '#'
+            } else {
+                *cell.value
+            }
+        });
+
+        assert_eq!(automaton.run_until_stable(10), Some(2));
+        assert!(automaton.grid().map(|&c| c == '#') == Grid2::new_with(3, 3, true));
+    }
+}
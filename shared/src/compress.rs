@@ -0,0 +1,157 @@
+use crate::Pos2;
+
+// Maps a sparse set of coordinates onto the dense indices 0..len, keeping their order
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CoordCompression<T> {
+    values: Vec<T>,
+}
+
+impl<T: Ord + Copy> CoordCompression<T> {
+    pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+        let mut values = values.into_iter().collect::<Vec<_>>();
+        values.sort_unstable();
+        values.dedup();
+        Self { values }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    // Sorted and without duplicates
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn index(&self, value: T) -> Option<usize> {
+        self.values.binary_search(&value).ok()
+    }
+
+    // Index of the largest known value that is less than or equal to `value`
+    pub fn floor_index(&self, value: T) -> Option<usize> {
+        self.values.partition_point(|&v| v <= value).checked_sub(1)
+    }
+
+    pub fn value(&self, index: usize) -> T {
+        self.values[index]
+    }
+}
+
+// Compresses both axes of a point set independently
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CoordCompression2<T> {
+    pub x: CoordCompression<T>,
+    pub y: CoordCompression<T>,
+}
+
+impl<T: Ord + Copy> CoordCompression2<T> {
+    pub fn new(points: impl IntoIterator<Item = Pos2<T>>) -> Self {
+        let (xs, ys): (Vec<_>, Vec<_>) = points.into_iter().map(|p| (p.x, p.y)).unzip();
+        Self {
+            x: CoordCompression::new(xs),
+            y: CoordCompression::new(ys),
+        }
+    }
+
+    // Width and height of the dense grid
+    pub fn size(&self) -> (usize, usize) {
+        (self.x.len(), self.y.len())
+    }
+
+    pub fn compress(&self, pos: Pos2<T>) -> Option<Pos2<usize>> {
+        Some(Pos2::new(self.x.index(pos.x)?, self.y.index(pos.y)?))
+    }
+
+    pub fn decompress(&self, pos: Pos2<usize>) -> Pos2<T> {
+        Pos2::new(self.x.value(pos.x), self.y.value(pos.y))
+    }
+}
+
+// Virtually stretches every coordinate that is not occupied, e.g. to make each empty row of
+// a grid count as `factor` rows, without ever building the expanded grid. Coordinate 0 stays
+// in place and everything else moves away from it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Expansion {
+    occupied: Vec<i64>,
+}
+
+impl Expansion {
+    pub fn new(occupied: impl IntoIterator<Item = i64>) -> Self {
+        let mut occupied = occupied.into_iter().collect::<Vec<_>>();
+        occupied.sort_unstable();
+        occupied.dedup();
+        Self { occupied }
+    }
+
+    // Number of empty coordinates between 0 and `value`, negative for negative values
+    pub fn empty_before(&self, value: i64) -> i64 {
+        let count_below = |v: i64| self.occupied.partition_point(|&o| o < v) as i64;
+        let occupied_between = (count_below(value) - count_below(0)).abs();
+        value - value.signum() * occupied_between
+    }
+
+    pub fn expand(&self, value: i64, factor: i64) -> i64 {
+        value + self.empty_before(value) * (factor - 1)
+    }
+}
+
+// Expands both axes of a point set, where a row or column is empty when no point lies in it
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Expansion2 {
+    pub x: Expansion,
+    pub y: Expansion,
+}
+
+impl Expansion2 {
+    pub fn new(points: impl IntoIterator<Item = Pos2<i64>>) -> Self {
+        let (xs, ys): (Vec<_>, Vec<_>) = points.into_iter().map(|p| (p.x, p.y)).unzip();
+        Self {
+            x: Expansion::new(xs),
+            y: Expansion::new(ys),
+        }
+    }
+
+    pub fn expand(&self, pos: Pos2<i64>, factor: i64) -> Pos2<i64> {
+        Pos2::new(self.x.expand(pos.x, factor), self.y.expand(pos.y, factor))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coord_compression() {
+        let compression = CoordCompression::new([100, -5, 7, 100, 3_000_000]);
+        assert_eq!(compression.len(), 4);
+        assert_eq!(compression.index(7), Some(1));
+        assert_eq!(compression.index(8), None);
+        assert_eq!(compression.floor_index(8), Some(1));
+        assert_eq!(compression.floor_index(-6), None);
+        assert_eq!(compression.value(3), 3_000_000);
+
+        let points = [Pos2::new(10, -3), Pos2::new(-2, 50), Pos2::new(10, 50)];
+        let compression = CoordCompression2::new(points);
+        assert_eq!(compression.size(), (2, 2));
+        for p in points {
+            assert_eq!(compression.decompress(compression.compress(p).unwrap()), p);
+        }
+        assert_eq!(compression.compress(Pos2::new(-2, 0)), None);
+    }
+
+    #[test]
+    fn test_expansion() {
+        let expansion = Expansion::new([1, 2, 5, -3]);
+        assert_eq!(expansion.empty_before(0), 0);
+        assert_eq!(expansion.empty_before(5), 3);
+        assert_eq!(expansion.empty_before(6), 3);
+        assert_eq!(expansion.empty_before(-3), -2);
+        assert_eq!(expansion.expand(5, 10), 5 + 27);
+        assert_eq!(expansion.expand(-4, 2), -7);
+        assert_eq!(expansion.expand(2, 1_000_000), 2 + 999_999);
+    }
+}
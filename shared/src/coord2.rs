@@ -2,13 +2,14 @@ use std::ops::*;
 
 use crate::One;
 
-#[derive(Copy, Clone, PartialEq, Eq, Default, Hash)]
+// Ord is lexicographic by x then y, see `cmp_reading_order` for the grid reading order
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
 pub struct Vec2<T> {
     pub x: T,
     pub y: T,
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Default, Hash)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
 pub struct Pos2<T>(Vec2<T>);
 
 impl<T> Vec2<T> {
@@ -30,6 +31,45 @@ impl<T> Vec2<T> {
         }
     }
 
+    // There are two coordinate conventions in use:
+    // - Math space, where y increases upwards. `up`/`down` and `rot_left`/`rot_right` use this.
+    // - Grid space, where y increases downwards, matching how Grid2 rows are indexed.
+    //   `north`/`south`/`east`/`west` and `turn_left`/`turn_right` use this, as does Dir4.
+
+    pub fn north() -> Self
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        Vec2 {
+            x: T::default(),
+            y: -T::one(),
+        }
+    }
+
+    pub fn south() -> Self
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        Vec2 {
+            x: T::default(),
+            y: T::one(),
+        }
+    }
+
+    pub fn east() -> Self
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        Self::right()
+    }
+
+    pub fn west() -> Self
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        Self::left()
+    }
+
     pub fn left() -> Self
     where
         T: Default + One + Neg<Output = T>,
@@ -178,6 +218,21 @@ impl<T> Vec2<T> {
         }
     }
 
+    // Grid space rotations, so north turns left into west
+    pub fn turn_left(&self) -> Self
+    where
+        T: Copy + Neg<Output = T>,
+    {
+        self.rot_right()
+    }
+
+    pub fn turn_right(&self) -> Self
+    where
+        T: Copy + Neg<Output = T>,
+    {
+        self.rot_left()
+    }
+
     pub fn flip(&self) -> Self
     where
         T: Copy + Neg<Output = T>,
@@ -232,6 +287,34 @@ impl<T> Pos2<T> {
         Pos2(Vec2::zero())
     }
 
+    pub fn north() -> Self
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        Pos2(Vec2::north())
+    }
+
+    pub fn south() -> Self
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        Pos2(Vec2::south())
+    }
+
+    pub fn east() -> Self
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        Pos2(Vec2::east())
+    }
+
+    pub fn west() -> Self
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        Pos2(Vec2::west())
+    }
+
     pub fn left() -> Self
     where
         T: Default + One + Neg<Output = T>,
@@ -267,6 +350,20 @@ impl<T> Pos2<T> {
         Pos2(self.0.rot_left())
     }
 
+    pub fn turn_left(&self) -> Self
+    where
+        T: Copy + Neg<Output = T>,
+    {
+        Pos2(self.0.turn_left())
+    }
+
+    pub fn turn_right(&self) -> Self
+    where
+        T: Copy + Neg<Output = T>,
+    {
+        Pos2(self.0.turn_right())
+    }
+
     pub fn rot_right(&self) -> Self
     where
         T: Copy + Neg<Output = T>,
@@ -328,6 +425,188 @@ impl<T> Pos2<T> {
     }
 }
 
+pub(crate) fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+pub(crate) fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
+    if b < a {
+        b
+    } else {
+        a
+    }
+}
+
+pub(crate) fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
+    if b > a {
+        b
+    } else {
+        a
+    }
+}
+
+impl<T> Vec2<T> {
+    pub fn dot(self, other: Self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        self.x * other.x + self.y * other.y
+    }
+
+    // The z component of the 3D cross product, positive if `other` is counter-clockwise
+    // from `self` in math space
+    pub fn cross(self, other: Self) -> T
+    where
+        T: Copy + Sub<Output = T> + Mul<Output = T>,
+    {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn abs(self) -> Self
+    where
+        T: Copy + Default + PartialOrd + Sub<Output = T>,
+    {
+        self.map(|c| abs_diff(c, T::default()))
+    }
+
+    pub fn signum(self) -> Self
+    where
+        T: Copy + Default + One + PartialOrd + Sub<Output = T>,
+    {
+        self.map(|c| {
+            if c > T::default() {
+                T::one()
+            } else if c < T::default() {
+                T::default() - T::one()
+            } else {
+                T::default()
+            }
+        })
+    }
+
+    pub fn manhattan_length(self) -> T
+    where
+        T: Copy + Default + PartialOrd + Add<Output = T> + Sub<Output = T>,
+    {
+        let abs = self.abs();
+        abs.x + abs.y
+    }
+
+    pub fn chebyshev_length(self) -> T
+    where
+        T: Copy + Default + PartialOrd + Sub<Output = T>,
+    {
+        let abs = self.abs();
+        partial_max(abs.x, abs.y)
+    }
+
+    pub fn length_squared(self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        self.dot(self)
+    }
+
+    pub fn component_min(self, other: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Vec2::new(partial_min(self.x, other.x), partial_min(self.y, other.y))
+    }
+
+    pub fn component_max(self, other: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Vec2::new(partial_max(self.x, other.x), partial_max(self.y, other.y))
+    }
+
+    // Clamps each component separately, unlike `Ord::clamp` which compares lexicographically
+    pub fn clamp(self, min: Self, max: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        self.component_max(min).component_min(max)
+    }
+
+    pub fn cast<U: From<T>>(self) -> Vec2<U> {
+        self.map(U::from)
+    }
+
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Vec2<U>> {
+        Some(Vec2::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+        ))
+    }
+
+    // Compares by y first, then x, which is the order cells are read in a grid
+    pub fn cmp_reading_order(&self, other: &Self) -> std::cmp::Ordering
+    where
+        T: Ord,
+    {
+        (&self.y, &self.x).cmp(&(&other.y, &other.x))
+    }
+}
+
+impl<T> Pos2<T> {
+    pub fn manhattan_distance(self, other: Self) -> T
+    where
+        T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+    {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    pub fn chebyshev_distance(self, other: Self) -> T
+    where
+        T: Copy + PartialOrd + Sub<Output = T>,
+    {
+        partial_max(abs_diff(self.x, other.x), abs_diff(self.y, other.y))
+    }
+
+    pub fn distance_squared(self, other: Self) -> T
+    where
+        T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        dx * dx + dy * dy
+    }
+
+    pub fn component_min(self, other: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Pos2(self.0.component_min(other.0))
+    }
+
+    pub fn component_max(self, other: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Pos2(self.0.component_max(other.0))
+    }
+
+    pub fn clamp(self, min: Self, max: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Pos2(self.0.clamp(min.0, max.0))
+    }
+
+    pub fn cast<U: From<T>>(self) -> Pos2<U> {
+        Pos2(self.0.cast())
+    }
+
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Pos2<U>> {
+        Some(Pos2(self.0.try_cast()?))
+    }
+}
+
 impl<T: std::fmt::Debug> std::fmt::Debug for Vec2<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Vec2({:?}, {:?})", self.x, self.y)
@@ -388,6 +667,14 @@ impl<T: Add<Output = T> + Copy> Add<Vec2<T>> for Pos2<T> {
     }
 }
 
+impl<T: Add<Output = T> + Copy> Add<Pos2<T>> for Vec2<T> {
+    type Output = Pos2<T>;
+
+    fn add(self, rhs: Pos2<T>) -> Self::Output {
+        rhs + self
+    }
+}
+
 impl<T: Add<Output = T> + Copy> AddAssign<Vec2<T>> for Vec2<T> {
     fn add_assign(&mut self, rhs: Vec2<T>) {
         self.x = self.x + rhs.x;
@@ -424,6 +711,14 @@ impl<T: Sub<Output = T> + Copy> Sub<Vec2<T>> for Pos2<T> {
     }
 }
 
+impl<T: Sub<Output = T> + Copy> Sub<Pos2<T>> for Pos2<T> {
+    type Output = Vec2<T>;
+
+    fn sub(self, rhs: Pos2<T>) -> Self::Output {
+        self.0 - rhs.0
+    }
+}
+
 impl<T: Sub<Output = T> + Copy> SubAssign<Vec2<T>> for Vec2<T> {
     fn sub_assign(&mut self, rhs: Vec2<T>) {
         self.x = self.x - rhs.x;
@@ -603,3 +898,46 @@ impl<T: Neg<Output = T> + Copy> Neg for Pos2<T> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_affine_algebra_and_metrics() {
+        let a = Pos2::new(1, 5);
+        let b = Pos2::new(4, 1);
+        let d = b - a;
+        assert_eq!(d, Vec2::new(3, -4));
+        assert_eq!(a + d, b);
+        assert_eq!(d + a, b);
+
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+        assert_eq!(a.distance_squared(b), 25);
+        assert_eq!(Pos2::new(2usize, 9).manhattan_distance(Pos2::new(5, 3)), 9);
+
+        assert_eq!(d.abs(), Vec2::new(3, 4));
+        assert_eq!(d.signum(), Vec2::new(1, -1));
+        assert_eq!(d.dot(Vec2::new(2, 1)), 2);
+        assert_eq!(Vec2::new(1, 0).cross(Vec2::new(0, 1)), 1);
+        assert_eq!(
+            Pos2::new(-3, 12).clamp(Pos2::new(0, 0), Pos2::new(10, 10)),
+            Pos2::new(0, 10)
+        );
+
+        let mut positions = [Pos2::new(2, 1), Pos2::new(1, 2), Pos2::new(1, 1)];
+        positions.sort();
+        assert_eq!(positions[0], Pos2::new(1, 1));
+        assert_eq!(positions[1], Pos2::new(1, 2));
+        positions.sort_by(|a, b| a.cmp_reading_order(b));
+        assert_eq!(positions[1], Pos2::new(2, 1));
+
+        assert_eq!(Vec2::new(3u8, 4).cast::<i64>(), Vec2::new(3i64, 4));
+        assert_eq!(Pos2::new(-1i32, 4).try_cast::<usize>(), None);
+        assert_eq!(
+            Pos2::new(1i32, 4).try_cast::<usize>(),
+            Some(Pos2::new(1, 4))
+        );
+    }
+}
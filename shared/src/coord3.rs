@@ -0,0 +1,773 @@
+use std::ops::*;
+
+use crate::{abs_diff, partial_max, partial_min, One};
+
+// Ord is lexicographic by x, then y, then z
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+pub struct Vec3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+pub struct Pos3<T>(Vec3<T>);
+
+// The 6 axis permutations, with whether each is an even permutation
+const AXIS_PERMUTATIONS: [([usize; 3], bool); 6] = [
+    ([0, 1, 2], true),
+    ([1, 2, 0], true),
+    ([2, 0, 1], true),
+    ([0, 2, 1], false),
+    ([2, 1, 0], false),
+    ([1, 0, 2], false),
+];
+
+// Bitmasks of which axes to negate. Even permutations need an even number of negations to
+// stay a proper rotation, odd permutations need an odd number.
+const EVEN_NEGATIONS: [u8; 4] = [0b000, 0b011, 0b101, 0b110];
+const ODD_NEGATIONS: [u8; 4] = [0b001, 0b010, 0b100, 0b111];
+
+impl<T> Vec3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    pub fn to_pos(self) -> Pos3<T> {
+        Pos3(self)
+    }
+
+    pub fn zero() -> Self
+    where
+        T: Default,
+    {
+        Vec3 {
+            x: T::default(),
+            y: T::default(),
+            z: T::default(),
+        }
+    }
+
+    fn unit(x: i8, y: i8, z: i8) -> Self
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        let component = |c: i8| match c {
+            -1 => -T::one(),
+            1 => T::one(),
+            _ => T::default(),
+        };
+
+        Vec3::new(component(x), component(y), component(z))
+    }
+
+    // Offsets sharing a face
+    pub fn six_directions() -> [Self; 6]
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        [
+            Self::unit(-1, 0, 0),
+            Self::unit(1, 0, 0),
+            Self::unit(0, -1, 0),
+            Self::unit(0, 1, 0),
+            Self::unit(0, 0, -1),
+            Self::unit(0, 0, 1),
+        ]
+    }
+
+    // Offsets sharing a face or an edge
+    pub fn eighteen_directions() -> [Self; 18]
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        let mut i = 0;
+        std::array::from_fn(|_| loop {
+            let (x, y, z) = (
+                (i % 3) as i8 - 1,
+                ((i / 3) % 3) as i8 - 1,
+                (i / 9) as i8 - 1,
+            );
+            i += 1;
+
+            let nonzero = (x != 0) as u8 + (y != 0) as u8 + (z != 0) as u8;
+            if nonzero == 1 || nonzero == 2 {
+                break Self::unit(x, y, z);
+            }
+        })
+    }
+
+    // Offsets sharing a face, an edge or a corner
+    pub fn twenty_six_directions() -> [Self; 26]
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        let mut i = 0;
+        std::array::from_fn(|_| {
+            // Skip the center
+            if i == 13 {
+                i += 1;
+            }
+            let (x, y, z) = (
+                (i % 3) as i8 - 1,
+                ((i / 3) % 3) as i8 - 1,
+                (i / 9) as i8 - 1,
+            );
+            i += 1;
+
+            Self::unit(x, y, z)
+        })
+    }
+
+    fn iter_directions<const L: usize>(self, dirs: [Self; L]) -> impl Iterator<Item = Self>
+    where
+        T: Add<Output = T> + Copy,
+    {
+        dirs.into_iter().map(move |dir| self + dir)
+    }
+
+    pub fn iter_six_directions<'a>(self) -> impl 'a + Iterator<Item = Self>
+    where
+        T: 'a + Default + One + Neg<Output = T> + Add<Output = T> + Copy,
+    {
+        self.iter_directions(Self::six_directions())
+    }
+
+    pub fn iter_eighteen_directions<'a>(self) -> impl 'a + Iterator<Item = Self>
+    where
+        T: 'a + Default + One + Neg<Output = T> + Add<Output = T> + Copy,
+    {
+        self.iter_directions(Self::eighteen_directions())
+    }
+
+    pub fn iter_twenty_six_directions<'a>(self) -> impl 'a + Iterator<Item = Self>
+    where
+        T: 'a + Default + One + Neg<Output = T> + Add<Output = T> + Copy,
+    {
+        self.iter_directions(Self::twenty_six_directions())
+    }
+
+    // Applies one of the 24 axis-aligned rotations, where index 0 is the identity. The same
+    // index always gives the same rotation, so it can be applied to a whole set of vectors.
+    pub fn rotate(self, index: usize) -> Self
+    where
+        T: Copy + Neg<Output = T>,
+    {
+        assert!(index < 24, "Rotation index out of range: {}", index);
+
+        let (perm, even) = AXIS_PERMUTATIONS[index / 4];
+        let negations = if even {
+            EVEN_NEGATIONS[index % 4]
+        } else {
+            ODD_NEGATIONS[index % 4]
+        };
+
+        let components = [self.x, self.y, self.z];
+        let axis = |i: usize| {
+            let c = components[perm[i]];
+            if negations & (1 << i) != 0 {
+                -c
+            } else {
+                c
+            }
+        };
+
+        Vec3::new(axis(0), axis(1), axis(2))
+    }
+
+    pub fn all_rotations(self) -> [Self; 24]
+    where
+        T: Copy + Neg<Output = T>,
+    {
+        std::array::from_fn(|i| self.rotate(i))
+    }
+
+    pub fn map<U, F: Fn(T) -> U>(self, f: F) -> Vec3<U> {
+        Vec3 {
+            x: f(self.x),
+            y: f(self.y),
+            z: f(self.z),
+        }
+    }
+
+    pub fn dot(self, other: Self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub fn cross(self, other: Self) -> Self
+    where
+        T: Copy + Sub<Output = T> + Mul<Output = T>,
+    {
+        Vec3 {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    pub fn abs(self) -> Self
+    where
+        T: Copy + Default + PartialOrd + Sub<Output = T>,
+    {
+        self.map(|c| abs_diff(c, T::default()))
+    }
+
+    pub fn signum(self) -> Self
+    where
+        T: Copy + Default + One + PartialOrd + Sub<Output = T>,
+    {
+        self.map(|c| {
+            if c > T::default() {
+                T::one()
+            } else if c < T::default() {
+                T::default() - T::one()
+            } else {
+                T::default()
+            }
+        })
+    }
+
+    pub fn manhattan_length(self) -> T
+    where
+        T: Copy + Default + PartialOrd + Add<Output = T> + Sub<Output = T>,
+    {
+        let abs = self.abs();
+        abs.x + abs.y + abs.z
+    }
+
+    pub fn chebyshev_length(self) -> T
+    where
+        T: Copy + Default + PartialOrd + Sub<Output = T>,
+    {
+        let abs = self.abs();
+        partial_max(partial_max(abs.x, abs.y), abs.z)
+    }
+
+    pub fn length_squared(self) -> T
+    where
+        T: Copy + Add<Output = T> + Mul<Output = T>,
+    {
+        self.dot(self)
+    }
+
+    pub fn component_min(self, other: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Vec3::new(
+            partial_min(self.x, other.x),
+            partial_min(self.y, other.y),
+            partial_min(self.z, other.z),
+        )
+    }
+
+    pub fn component_max(self, other: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Vec3::new(
+            partial_max(self.x, other.x),
+            partial_max(self.y, other.y),
+            partial_max(self.z, other.z),
+        )
+    }
+
+    pub fn clamp(self, min: Self, max: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        self.component_max(min).component_min(max)
+    }
+
+    pub fn cast<U: From<T>>(self) -> Vec3<U> {
+        self.map(U::from)
+    }
+
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Vec3<U>> {
+        Some(Vec3::new(
+            U::try_from(self.x).ok()?,
+            U::try_from(self.y).ok()?,
+            U::try_from(self.z).ok()?,
+        ))
+    }
+}
+
+impl<T> Pos3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Pos3(Vec3 { x, y, z })
+    }
+
+    pub fn to_vec(self) -> Vec3<T> {
+        self.0
+    }
+
+    pub fn zero() -> Self
+    where
+        T: Default,
+    {
+        Pos3(Vec3::zero())
+    }
+
+    pub fn iter_six_directions<'a>(self) -> impl 'a + Iterator<Item = Self>
+    where
+        T: 'a + Default + One + Neg<Output = T> + Add<Output = T> + Copy,
+    {
+        self.0.iter_six_directions().map(Pos3)
+    }
+
+    pub fn iter_eighteen_directions<'a>(self) -> impl 'a + Iterator<Item = Self>
+    where
+        T: 'a + Default + One + Neg<Output = T> + Add<Output = T> + Copy,
+    {
+        self.0.iter_eighteen_directions().map(Pos3)
+    }
+
+    pub fn iter_twenty_six_directions<'a>(self) -> impl 'a + Iterator<Item = Self>
+    where
+        T: 'a + Default + One + Neg<Output = T> + Add<Output = T> + Copy,
+    {
+        self.0.iter_twenty_six_directions().map(Pos3)
+    }
+
+    // Rotates around the origin, see `Vec3::rotate`
+    pub fn rotate(self, index: usize) -> Self
+    where
+        T: Copy + Neg<Output = T>,
+    {
+        Pos3(self.0.rotate(index))
+    }
+
+    pub fn map<U, F: Fn(T) -> U>(self, f: F) -> Pos3<U> {
+        Pos3(self.0.map(f))
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> T
+    where
+        T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+    {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    pub fn chebyshev_distance(self, other: Self) -> T
+    where
+        T: Copy + PartialOrd + Sub<Output = T>,
+    {
+        partial_max(
+            partial_max(abs_diff(self.x, other.x), abs_diff(self.y, other.y)),
+            abs_diff(self.z, other.z),
+        )
+    }
+
+    pub fn distance_squared(self, other: Self) -> T
+    where
+        T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T> + Mul<Output = T>,
+    {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        let dz = abs_diff(self.z, other.z);
+        dx * dx + dy * dy + dz * dz
+    }
+
+    pub fn component_min(self, other: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Pos3(self.0.component_min(other.0))
+    }
+
+    pub fn component_max(self, other: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Pos3(self.0.component_max(other.0))
+    }
+
+    pub fn clamp(self, min: Self, max: Self) -> Self
+    where
+        T: PartialOrd,
+    {
+        Pos3(self.0.clamp(min.0, max.0))
+    }
+
+    pub fn cast<U: From<T>>(self) -> Pos3<U> {
+        Pos3(self.0.cast())
+    }
+
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Pos3<U>> {
+        Some(Pos3(self.0.try_cast()?))
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Vec3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Vec3({:?}, {:?}, {:?})", self.x, self.y, self.z)
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Pos3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Pos3({:?}, {:?}, {:?})", self.x, self.y, self.z)
+    }
+}
+
+impl<T> Deref for Pos3<T> {
+    type Target = Vec3<T>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Pos3<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Vec3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Pos3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+impl<T: Add<Output = T> + Copy> Add for Vec3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Vec3 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        }
+    }
+}
+
+impl<T: Add<Output = T> + Copy> Add<Vec3<T>> for Pos3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Vec3<T>) -> Self::Output {
+        Pos3(Vec3 {
+            x: self.x + rhs.x,
+            y: self.y + rhs.y,
+            z: self.z + rhs.z,
+        })
+    }
+}
+
+impl<T: Add<Output = T> + Copy> Add<Pos3<T>> for Vec3<T> {
+    type Output = Pos3<T>;
+
+    fn add(self, rhs: Pos3<T>) -> Self::Output {
+        rhs + self
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign<Vec3<T>> for Vec3<T> {
+    fn add_assign(&mut self, rhs: Vec3<T>) {
+        self.x = self.x + rhs.x;
+        self.y = self.y + rhs.y;
+        self.z = self.z + rhs.z;
+    }
+}
+
+impl<T: Add<Output = T> + Copy> AddAssign<Vec3<T>> for Pos3<T> {
+    fn add_assign(&mut self, rhs: Vec3<T>) {
+        self.x = self.x + rhs.x;
+        self.y = self.y + rhs.y;
+        self.z = self.z + rhs.z;
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> Sub for Vec3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vec3 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> Sub<Vec3<T>> for Pos3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Vec3<T>) -> Self::Output {
+        Pos3(Vec3 {
+            x: self.x - rhs.x,
+            y: self.y - rhs.y,
+            z: self.z - rhs.z,
+        })
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> Sub<Pos3<T>> for Pos3<T> {
+    type Output = Vec3<T>;
+
+    fn sub(self, rhs: Pos3<T>) -> Self::Output {
+        self.0 - rhs.0
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> SubAssign<Vec3<T>> for Vec3<T> {
+    fn sub_assign(&mut self, rhs: Vec3<T>) {
+        self.x = self.x - rhs.x;
+        self.y = self.y - rhs.y;
+        self.z = self.z - rhs.z;
+    }
+}
+
+impl<T: Sub<Output = T> + Copy> SubAssign<Vec3<T>> for Pos3<T> {
+    fn sub_assign(&mut self, rhs: Vec3<T>) {
+        self.x = self.x - rhs.x;
+        self.y = self.y - rhs.y;
+        self.z = self.z - rhs.z;
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Vec3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Vec3 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<Vec3<T>> for Vec3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Vec3<T>) -> Self::Output {
+        Vec3 {
+            x: self.x * rhs.x,
+            y: self.y * rhs.y,
+            z: self.z * rhs.z,
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Pos3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Pos3(Vec3 {
+            x: self.x * rhs,
+            y: self.y * rhs,
+            z: self.z * rhs,
+        })
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<Vec3<T>> for Pos3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Vec3<T>) -> Self::Output {
+        Pos3(Vec3 {
+            x: self.x * rhs.x,
+            y: self.y * rhs.y,
+            z: self.z * rhs.z,
+        })
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> MulAssign<T> for Vec3<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x = self.x * rhs;
+        self.y = self.y * rhs;
+        self.z = self.z * rhs;
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> MulAssign<Vec3<T>> for Vec3<T> {
+    fn mul_assign(&mut self, rhs: Vec3<T>) {
+        self.x = self.x * rhs.x;
+        self.y = self.y * rhs.y;
+        self.z = self.z * rhs.z;
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> MulAssign<T> for Pos3<T> {
+    fn mul_assign(&mut self, rhs: T) {
+        self.x = self.x * rhs;
+        self.y = self.y * rhs;
+        self.z = self.z * rhs;
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> MulAssign<Vec3<T>> for Pos3<T> {
+    fn mul_assign(&mut self, rhs: Vec3<T>) {
+        self.x = self.x * rhs.x;
+        self.y = self.y * rhs.y;
+        self.z = self.z * rhs.z;
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Vec3<T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Vec3 {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs,
+        }
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<Vec3<T>> for Vec3<T> {
+    type Output = Self;
+
+    fn div(self, rhs: Vec3<T>) -> Self::Output {
+        Vec3 {
+            x: self.x / rhs.x,
+            y: self.y / rhs.y,
+            z: self.z / rhs.z,
+        }
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Pos3<T> {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self::Output {
+        Pos3(Vec3 {
+            x: self.x / rhs,
+            y: self.y / rhs,
+            z: self.z / rhs,
+        })
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<Vec3<T>> for Pos3<T> {
+    type Output = Self;
+
+    fn div(self, rhs: Vec3<T>) -> Self::Output {
+        Pos3(Vec3 {
+            x: self.x / rhs.x,
+            y: self.y / rhs.y,
+            z: self.z / rhs.z,
+        })
+    }
+}
+
+impl<T: Div<Output = T> + Copy> DivAssign<T> for Vec3<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.x = self.x / rhs;
+        self.y = self.y / rhs;
+        self.z = self.z / rhs;
+    }
+}
+
+impl<T: Div<Output = T> + Copy> DivAssign<Vec3<T>> for Vec3<T> {
+    fn div_assign(&mut self, rhs: Vec3<T>) {
+        self.x = self.x / rhs.x;
+        self.y = self.y / rhs.y;
+        self.z = self.z / rhs.z;
+    }
+}
+
+impl<T: Div<Output = T> + Copy> DivAssign<T> for Pos3<T> {
+    fn div_assign(&mut self, rhs: T) {
+        self.x = self.x / rhs;
+        self.y = self.y / rhs;
+        self.z = self.z / rhs;
+    }
+}
+
+impl<T: Div<Output = T> + Copy> DivAssign<Vec3<T>> for Pos3<T> {
+    fn div_assign(&mut self, rhs: Vec3<T>) {
+        self.x = self.x / rhs.x;
+        self.y = self.y / rhs.y;
+        self.z = self.z / rhs.z;
+    }
+}
+
+impl<T: Neg<Output = T> + Copy> Neg for Vec3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Vec3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl<T: Neg<Output = T> + Copy> Neg for Pos3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Pos3(Vec3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_neighbors_and_rotations() {
+        let origin = Pos3::new(0, 0, 0);
+        assert_eq!(origin.iter_six_directions().count(), 6);
+        assert!(origin
+            .iter_eighteen_directions()
+            .all(|p| p.manhattan_distance(origin) <= 2 && p != origin));
+        assert_eq!(
+            origin
+                .iter_eighteen_directions()
+                .collect::<HashSet<_>>()
+                .len(),
+            18
+        );
+        assert_eq!(
+            origin
+                .iter_twenty_six_directions()
+                .filter(|p| p.chebyshev_distance(origin) == 1)
+                .count(),
+            26
+        );
+
+        let v = Vec3::new(1, 2, 3);
+        assert_eq!(v.rotate(0), v);
+        let rotations = v.all_rotations();
+        assert_eq!(rotations.iter().collect::<HashSet<_>>().len(), 24);
+
+        // Proper rotations preserve handedness
+        let (a, b) = (Vec3::new(1, 0, 0), Vec3::new(0, 1, 0));
+        for i in 0..24 {
+            assert_eq!(a.rotate(i).cross(b.rotate(i)), a.cross(b).rotate(i));
+        }
+    }
+
+    #[test]
+    fn test_algebra() {
+        let a = Pos3::new(1, 2, 3);
+        let b = Pos3::new(4, 0, 3);
+        assert_eq!(b - a, Vec3::new(3, -2, 0));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a.manhattan_distance(b), 5);
+        assert_eq!(a.distance_squared(b), 13);
+        assert_eq!(a.component_min(b), Pos3::new(1, 0, 3));
+        assert_eq!(-Vec3::new(1, -1, 0) * 2, Vec3::new(-2, 2, 0));
+    }
+}
@@ -0,0 +1,379 @@
+use std::{ops::*, str::FromStr};
+
+use crate::{One, Vec2};
+
+// Directions use grid space, where y increases downwards, so Up is (0, -1)
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDirError(pub String);
+
+impl std::fmt::Display for ParseDirError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid direction: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseDirError {}
+
+fn unit_vec<T>(x: i8, y: i8) -> Vec2<T>
+where
+    T: Default + One + Neg<Output = T>,
+{
+    let component = |c: i8| match c {
+        -1 => -T::one(),
+        1 => T::one(),
+        _ => T::default(),
+    };
+
+    Vec2::new(component(x), component(y))
+}
+
+impl Dir4 {
+    // Clockwise, starting from Up, matching `index()`
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL[index % 4]
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 3)
+    }
+
+    pub fn turn_around(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_around()
+    }
+
+    pub fn is_vertical(self) -> bool {
+        matches!(self, Dir4::Up | Dir4::Down)
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        !self.is_vertical()
+    }
+
+    pub fn to_vec<T>(self) -> Vec2<T>
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        match self {
+            Dir4::Up => Vec2::north(),
+            Dir4::Right => Vec2::east(),
+            Dir4::Down => Vec2::south(),
+            Dir4::Left => Vec2::west(),
+        }
+    }
+
+    pub fn from_vec<T>(vec: Vec2<T>) -> Option<Self>
+    where
+        T: Default + One + Neg<Output = T> + PartialEq,
+    {
+        Self::ALL.into_iter().find(|dir| dir.to_vec::<T>() == vec)
+    }
+
+    // Accepts exactly UDLR, NESW and ^>v< characters
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | 'N' | '^' => Some(Dir4::Up),
+            'R' | 'E' | '>' => Some(Dir4::Right),
+            'D' | 'S' | 'v' => Some(Dir4::Down),
+            'L' | 'W' | '<' => Some(Dir4::Left),
+            _ => None,
+        }
+    }
+
+    pub fn to_arrow(self) -> char {
+        match self {
+            Dir4::Up => '^',
+            Dir4::Right => '>',
+            Dir4::Down => 'v',
+            Dir4::Left => '<',
+        }
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::from_char(c).ok_or_else(|| ParseDirError(s.to_string())),
+            _ => Err(ParseDirError(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<char> for Dir4 {
+    type Error = ParseDirError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Self::from_char(c).ok_or_else(|| ParseDirError(c.to_string()))
+    }
+}
+
+impl Dir8 {
+    // Clockwise, starting from Up, matching `index()`
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Up,
+        Dir8::UpRight,
+        Dir8::Right,
+        Dir8::DownRight,
+        Dir8::Down,
+        Dir8::DownLeft,
+        Dir8::Left,
+        Dir8::UpLeft,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_index(index: usize) -> Self {
+        Self::ALL[index % 8]
+    }
+
+    // Turns by 45 degrees
+    pub fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    // Turns by 45 degrees
+    pub fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 7)
+    }
+
+    pub fn turn_right_90(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    pub fn turn_left_90(self) -> Self {
+        Self::from_index(self.index() + 6)
+    }
+
+    pub fn turn_around(self) -> Self {
+        Self::from_index(self.index() + 4)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.turn_around()
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    pub fn to_dir4(self) -> Option<Dir4> {
+        if self.is_diagonal() {
+            None
+        } else {
+            Some(Dir4::from_index(self.index() / 2))
+        }
+    }
+
+    pub fn to_vec<T>(self) -> Vec2<T>
+    where
+        T: Default + One + Neg<Output = T>,
+    {
+        match self {
+            Dir8::Up => unit_vec(0, -1),
+            Dir8::UpRight => unit_vec(1, -1),
+            Dir8::Right => unit_vec(1, 0),
+            Dir8::DownRight => unit_vec(1, 1),
+            Dir8::Down => unit_vec(0, 1),
+            Dir8::DownLeft => unit_vec(-1, 1),
+            Dir8::Left => unit_vec(-1, 0),
+            Dir8::UpLeft => unit_vec(-1, -1),
+        }
+    }
+
+    pub fn from_vec<T>(vec: Vec2<T>) -> Option<Self>
+    where
+        T: Default + One + Neg<Output = T> + PartialEq,
+    {
+        Self::ALL.into_iter().find(|dir| dir.to_vec::<T>() == vec)
+    }
+
+    pub fn from_char(c: char) -> Option<Self> {
+        Dir4::from_char(c).map(Dir8::from)
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Dir8::from_index(dir.index() * 2)
+    }
+}
+
+// Accepts a single Dir4 character, or a vertical and horizontal pair like "NE", "UR" or "^>"
+impl FromStr for Dir8 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseDirError(s.to_string());
+        let dirs = s
+            .chars()
+            .map(Dir4::from_char)
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(err)?;
+
+        match dirs[..] {
+            [dir] => Ok(dir.into()),
+            [Dir4::Up, Dir4::Right] => Ok(Dir8::UpRight),
+            [Dir4::Down, Dir4::Right] => Ok(Dir8::DownRight),
+            [Dir4::Down, Dir4::Left] => Ok(Dir8::DownLeft),
+            [Dir4::Up, Dir4::Left] => Ok(Dir8::UpLeft),
+            _ => Err(err()),
+        }
+    }
+}
+
+macro_rules! dir_set {
+    ($set:ident, $dir:ident) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub struct $set(u8);
+
+        impl $set {
+            pub fn new() -> Self {
+                Self(0)
+            }
+
+            pub fn all() -> Self {
+                $dir::ALL.into_iter().collect()
+            }
+
+            // Returns whether the direction was newly inserted
+            pub fn insert(&mut self, dir: $dir) -> bool {
+                let was_present = self.contains(dir);
+                self.0 |= 1 << dir.index();
+                !was_present
+            }
+
+            // Returns whether the direction was present
+            pub fn remove(&mut self, dir: $dir) -> bool {
+                let was_present = self.contains(dir);
+                self.0 &= !(1 << dir.index());
+                was_present
+            }
+
+            pub fn contains(&self, dir: $dir) -> bool {
+                self.0 & (1 << dir.index()) != 0
+            }
+
+            pub fn len(&self) -> usize {
+                self.0.count_ones() as usize
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.0 == 0
+            }
+
+            pub fn union(self, other: Self) -> Self {
+                Self(self.0 | other.0)
+            }
+
+            pub fn intersection(self, other: Self) -> Self {
+                Self(self.0 & other.0)
+            }
+
+            pub fn iter(self) -> impl Iterator<Item = $dir> {
+                $dir::ALL.into_iter().filter(move |&dir| self.contains(dir))
+            }
+        }
+
+        impl FromIterator<$dir> for $set {
+            fn from_iter<I: IntoIterator<Item = $dir>>(iter: I) -> Self {
+                let mut set = Self::new();
+                for dir in iter {
+                    set.insert(dir);
+                }
+                set
+            }
+        }
+    };
+}
+
+dir_set!(Dir4Set, Dir4);
+dir_set!(Dir8Set, Dir8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dir4() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.opposite(), Dir4::Right);
+        assert_eq!(Dir4::Up.to_vec::<i32>(), Vec2::new(0, -1));
+        assert_eq!(Dir4::from_vec(Vec2::new(-1i64, 0)), Some(Dir4::Left));
+        for dir in Dir4::ALL {
+            let vec = dir.to_vec::<i32>();
+            assert_eq!(dir.turn_left().to_vec(), vec.turn_left());
+            assert_eq!(dir.turn_right().to_vec(), vec.turn_right());
+        }
+        assert_eq!(Dir4::from_vec(Vec2::new(1, 1)), None);
+
+        for (s, dir) in [("U", Dir4::Up), ("E", Dir4::Right), ("v", Dir4::Down)] {
+            assert_eq!(s.parse::<Dir4>(), Ok(dir));
+        }
+        assert!("UR".parse::<Dir4>().is_err());
+        for c in ['V', 'u', 'n', 'e', 's', 'w'] {
+            assert_eq!(Dir4::from_char(c), None);
+        }
+        assert_eq!(Dir4::try_from('<'), Ok(Dir4::Left));
+    }
+
+    #[test]
+    fn test_dir8_and_sets() {
+        assert_eq!("NE".parse::<Dir8>(), Ok(Dir8::UpRight));
+        assert_eq!("SW".parse::<Dir8>(), Ok(Dir8::DownLeft));
+        assert_eq!("v<".parse::<Dir8>(), Ok(Dir8::DownLeft));
+        assert_eq!("W".parse::<Dir8>(), Ok(Dir8::Left));
+        assert!("NS".parse::<Dir8>().is_err());
+        assert_eq!(Dir8::UpRight.turn_right_90(), Dir8::DownRight);
+        assert_eq!(Dir8::UpLeft.to_vec::<i32>(), Vec2::new(-1, -1));
+        assert_eq!(Dir8::from(Dir4::Down).to_dir4(), Some(Dir4::Down));
+
+        let mut set = Dir4Set::new();
+        assert!(set.insert(Dir4::Left));
+        assert!(!set.insert(Dir4::Left));
+        assert!(set.insert(Dir4::Up));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![Dir4::Up, Dir4::Left]);
+        assert!(set.remove(Dir4::Up));
+        assert_eq!(set.len(), 1);
+        assert_eq!(Dir8Set::all().len(), 8);
+    }
+}
@@ -123,6 +123,16 @@ impl<T> Grid2<T> {
         let index = self.get_pos_index_or_panic(pos);
         self.data[index] = value;
     }
+
+    pub fn swap(
+        &mut self,
+        a: Pos2<impl ToUnsignedIndex + std::fmt::Debug + Copy>,
+        b: Pos2<impl ToUnsignedIndex + std::fmt::Debug + Copy>,
+    ) {
+        let a = self.get_pos_index_or_panic(a);
+        let b = self.get_pos_index_or_panic(b);
+        self.data.swap(a, b);
+    }
 }
 
 impl<T, I: ToUnsignedIndex + std::fmt::Debug + Copy> Index<Pos2<I>> for Grid2<T> {
@@ -140,15 +150,101 @@ impl<T, I: ToUnsignedIndex + std::fmt::Debug + Copy> IndexMut<Pos2<I>> for Grid2
     }
 }
 
-impl<T> From<Vec<Vec<T>>> for Grid2<T> {
-    fn from(vecs: Vec<Vec<T>>) -> Self {
-        let width = vecs[0].len();
-        let height = vecs.len();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grid2Error {
+    NoRows,
+    RaggedRow {
+        row: usize,
+        width: usize,
+        expected_width: usize,
+    },
+    InvalidCell {
+        row: usize,
+        col: usize,
+        value: char,
+    },
+}
 
-        let mut data = Vec::with_capacity(width * height);
+impl std::fmt::Display for Grid2Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Grid2Error::NoRows => write!(f, "Grid has no rows"),
+            Grid2Error::RaggedRow {
+                row,
+                width,
+                expected_width,
+            } => write!(
+                f,
+                "Grid row {} has width {}, expected {} (the width of row 0)",
+                row, width, expected_width
+            ),
+            Grid2Error::InvalidCell { row, col, value } => write!(
+                f,
+                "Invalid grid cell {:?} at row {}, column {}",
+                value, row, col
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Grid2Error {}
+
+impl<T> Grid2<T> {
+    pub fn from_rows<R: IntoIterator<Item = T>>(
+        rows: impl IntoIterator<Item = R>,
+    ) -> Result<Self, Grid2Error> {
+        let mut data = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+
+        for (y, row) in rows.into_iter().enumerate() {
+            let len_before = data.len();
+            data.extend(row);
+            let row_width = data.len() - len_before;
+
+            match width {
+                None => width = Some(row_width),
+                Some(expected_width) if expected_width != row_width => {
+                    return Err(Grid2Error::RaggedRow {
+                        row: y,
+                        width: row_width,
+                        expected_width,
+                    });
+                }
+                _ => {}
+            }
+
+            height += 1;
+        }
+
+        let width = width.ok_or(Grid2Error::NoRows)?;
+
+        Ok(Self {
+            data,
+            width,
+            height,
+        })
+    }
+
+    // Pads every row with `fill` up to the width of the longest row
+    pub fn from_ragged<R: IntoIterator<Item = T>>(
+        rows: impl IntoIterator<Item = R>,
+        fill: T,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        let rows = rows
+            .into_iter()
+            .map(|row| row.into_iter().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let height = rows.len();
 
-        for row in vecs {
-            assert_eq!(row.len(), width);
+        let mut data = Vec::with_capacity(width * height);
+        for mut row in rows {
+            row.resize(width, fill.clone());
             data.extend(row);
         }
 
@@ -159,3 +255,42 @@ impl<T> From<Vec<Vec<T>>> for Grid2<T> {
         }
     }
 }
+
+impl<T> TryFrom<Vec<Vec<T>>> for Grid2<T> {
+    type Error = Grid2Error;
+
+    fn try_from(vecs: Vec<Vec<T>>) -> Result<Self, Self::Error> {
+        Self::from_rows(vecs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid2_from_rows() {
+        let grid = Grid2::try_from(vec![vec![1, 2], vec![3, 4]]).unwrap();
+        assert_eq!(grid[Pos2::new(1, 1)], 4);
+        assert_eq!(grid[Pos2::new(1u8, 0u8)], 2);
+        assert_eq!(grid.get(Pos2::new(-1i16, 0)), None);
+
+        assert_eq!(
+            Grid2::<u8>::try_from(vec![]).unwrap_err(),
+            Grid2Error::NoRows
+        );
+        assert_eq!(
+            Grid2::from_rows(["ab", "cd", ""].iter().map(|l| l.chars())).unwrap_err(),
+            Grid2Error::RaggedRow {
+                row: 2,
+                width: 0,
+                expected_width: 2
+            }
+        );
+
+        let ragged = Grid2::from_ragged(vec![vec![1], vec![2, 3, 4]], 0);
+        assert_eq!((ragged.width(), ragged.height()), (3, 2));
+        assert_eq!(ragged[Pos2::new(2, 0)], 0);
+        assert_eq!(ragged[Pos2::new(2, 1)], 4);
+    }
+}
@@ -0,0 +1,175 @@
+use std::ops::*;
+
+use crate::{Pos3, ToUnsignedIndex};
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Grid3<T> {
+    data: Vec<T>,
+    width: usize,
+    height: usize,
+    depth: usize,
+}
+
+impl<T> Grid3<T> {
+    pub fn new_empty() -> Self {
+        Self {
+            data: vec![],
+            width: 0,
+            height: 0,
+            depth: 0,
+        }
+    }
+
+    pub fn new_default(width: usize, height: usize, depth: usize) -> Self
+    where
+        T: Default,
+    {
+        Self::from_fn(width, height, depth, |_| T::default())
+    }
+
+    pub fn new_with(width: usize, height: usize, depth: usize, val: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            data: vec![val; width * height * depth],
+            width,
+            height,
+            depth,
+        }
+    }
+
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        depth: usize,
+        mut f: impl FnMut(Pos3<usize>) -> T,
+    ) -> Self {
+        let mut data = Vec::with_capacity(width * height * depth);
+        for z in 0..depth {
+            for y in 0..height {
+                for x in 0..width {
+                    data.push(f(Pos3::new(x, y, z)));
+                }
+            }
+        }
+        Self {
+            data,
+            width,
+            height,
+            depth,
+        }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid3<U> {
+        Grid3 {
+            data: self.data.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+        }
+    }
+
+    fn get_pos_index<I: ToUnsignedIndex + Copy>(&self, pos: Pos3<I>) -> Option<usize> {
+        let pos = Pos3::new(pos.x.to_index()?, pos.y.to_index()?, pos.z.to_index()?);
+
+        if pos.x >= self.width || pos.y >= self.height || pos.z >= self.depth {
+            return None;
+        }
+
+        Some((pos.z * self.height + pos.y) * self.width + pos.x)
+    }
+
+    fn get_pos_index_or_panic<I: ToUnsignedIndex + std::fmt::Debug + Copy>(
+        &self,
+        pos: Pos3<I>,
+    ) -> usize {
+        let index = self.get_pos_index(pos);
+        if let Some(index) = index {
+            index
+        } else {
+            panic!(
+                "Index out of bounds: {:?}, of array size {:?}",
+                pos,
+                Pos3::new(self.width, self.height, self.depth)
+            );
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn get(&self, pos: Pos3<impl ToUnsignedIndex + std::fmt::Debug + Copy>) -> Option<&T> {
+        Some(&self.data[self.get_pos_index(pos)?])
+    }
+
+    pub fn get_mut(
+        &mut self,
+        pos: Pos3<impl ToUnsignedIndex + std::fmt::Debug + Copy>,
+    ) -> Option<&mut T> {
+        let index = self.get_pos_index(pos)?;
+        Some(&mut self.data[index])
+    }
+
+    pub fn is_in_bounds(&self, pos: Pos3<impl ToUnsignedIndex + std::fmt::Debug + Copy>) -> bool {
+        self.get_pos_index(pos).is_some()
+    }
+
+    pub fn set(&mut self, pos: Pos3<impl ToUnsignedIndex + std::fmt::Debug + Copy>, value: T) {
+        let index = self.get_pos_index_or_panic(pos);
+        self.data[index] = value;
+    }
+
+    // Iterates x fastest, then y, then z
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (Pos3<usize>, &T)> {
+        self.data.iter().enumerate().map(|(i, value)| {
+            let x = i % self.width;
+            let y = (i / self.width) % self.height;
+            let z = i / (self.width * self.height);
+            (Pos3::new(x, y, z), value)
+        })
+    }
+}
+
+impl<T, I: ToUnsignedIndex + std::fmt::Debug + Copy> Index<Pos3<I>> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, index: Pos3<I>) -> &Self::Output {
+        &self.data[self.get_pos_index_or_panic(index)]
+    }
+}
+
+impl<T, I: ToUnsignedIndex + std::fmt::Debug + Copy> IndexMut<Pos3<I>> for Grid3<T> {
+    fn index_mut(&mut self, index: Pos3<I>) -> &mut Self::Output {
+        let index = self.get_pos_index_or_panic(index);
+        &mut self.data[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid3() {
+        let mut grid = Grid3::from_fn(2, 3, 4, |p| p.x + p.y * 10 + p.z * 100);
+        assert_eq!(grid[Pos3::new(1, 2, 3)], 321);
+        assert!(grid.is_in_bounds(Pos3::new(1, 2, 3)));
+        assert!(!grid.is_in_bounds(Pos3::new(2, 0, 0)));
+        assert!(!grid.is_in_bounds(Pos3::new(0, 0, -1)));
+        assert_eq!(grid.get(Pos3::new(0, 3, 0)), None);
+
+        grid[Pos3::new(0, 0, 1)] = 7;
+        assert!(grid.iter().all(|(p, &v)| v == grid[p]));
+        assert_eq!(grid.iter().filter(|(_, &v)| v == 7).count(), 1);
+    }
+}
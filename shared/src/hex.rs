@@ -0,0 +1,402 @@
+use std::{collections::HashMap, ops::*, str::FromStr};
+
+use crate::ParseDirError;
+
+// Axial hex coordinates. The implicit third cube coordinate is `s = -q - r`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Hash)]
+pub struct Hex {
+    pub q: i64,
+    pub r: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HexOrientation {
+    // Flat edges at the top and bottom, neighbors are n/ne/se/s/sw/nw
+    FlatTop,
+    // Pointy corners at the top and bottom, neighbors are e/ne/nw/w/sw/se
+    PointyTop,
+}
+
+// Axial offsets, counter-clockwise, starting from the direction along +q
+const AXIAL_DIRECTIONS: [Hex; 6] = [
+    Hex { q: 1, r: 0 },
+    Hex { q: 1, r: -1 },
+    Hex { q: 0, r: -1 },
+    Hex { q: -1, r: 0 },
+    Hex { q: -1, r: 1 },
+    Hex { q: 0, r: 1 },
+];
+
+impl Hex {
+    pub fn new(q: i64, r: i64) -> Self {
+        Hex { q, r }
+    }
+
+    pub fn from_cube(q: i64, r: i64, s: i64) -> Self {
+        assert_eq!(q + r + s, 0, "Cube coordinates must sum to zero");
+        Hex { q, r }
+    }
+
+    pub fn zero() -> Self {
+        Hex { q: 0, r: 0 }
+    }
+
+    pub fn s(self) -> i64 {
+        -self.q - self.r
+    }
+
+    pub fn cube(self) -> (i64, i64, i64) {
+        (self.q, self.r, self.s())
+    }
+
+    // Number of steps from the origin
+    pub fn length(self) -> i64 {
+        (self.q.abs() + self.r.abs() + self.s().abs()) / 2
+    }
+
+    pub fn distance(self, other: Hex) -> i64 {
+        (self - other).length()
+    }
+
+    pub fn neighbors(self) -> [Hex; 6] {
+        AXIAL_DIRECTIONS.map(|d| self + d)
+    }
+
+    // All hexes at exactly the given distance, walking counter-clockwise
+    pub fn ring(self, radius: i64) -> Vec<Hex> {
+        if radius == 0 {
+            return vec![self];
+        }
+
+        let mut result = Vec::with_capacity(6 * radius as usize);
+        let mut hex = self + AXIAL_DIRECTIONS[4] * radius;
+        for dir in AXIAL_DIRECTIONS {
+            for _ in 0..radius {
+                result.push(hex);
+                hex += dir;
+            }
+        }
+
+        result
+    }
+
+    // All hexes up to the given distance, ring by ring from the center outwards
+    pub fn spiral(self, radius: i64) -> Vec<Hex> {
+        (0..=radius).flat_map(|r| self.ring(r)).collect()
+    }
+
+    // The hexes on the straight line between the two hexes, including both ends
+    pub fn line_to(self, other: Hex) -> Vec<Hex> {
+        let n = self.distance(other);
+        if n == 0 {
+            return vec![self];
+        }
+
+        // Nudge off the exact midpoints so that ties round consistently
+        let (q0, r0, s0) = (
+            self.q as f64 + 1e-6,
+            self.r as f64 + 2e-6,
+            self.s() as f64 - 3e-6,
+        );
+        let (dq, dr, ds) = (
+            (other.q - self.q) as f64,
+            (other.r - self.r) as f64,
+            (other.s() - self.s()) as f64,
+        );
+
+        (0..=n)
+            .map(|i| {
+                let t = i as f64 / n as f64;
+                cube_round(q0 + dq * t, r0 + dr * t, s0 + ds * t)
+            })
+            .collect()
+    }
+
+    // Center of the hex in pixel space, with y pointing down and a hex radius of 1
+    pub fn to_pixel(self, orientation: HexOrientation) -> (f64, f64) {
+        let sqrt3 = 3f64.sqrt();
+        let (q, r) = (self.q as f64, self.r as f64);
+        match orientation {
+            HexOrientation::FlatTop => (1.5 * q, sqrt3 / 2.0 * q + sqrt3 * r),
+            HexOrientation::PointyTop => (sqrt3 * q + sqrt3 / 2.0 * r, 1.5 * r),
+        }
+    }
+}
+
+fn cube_round(q: f64, r: f64, s: f64) -> Hex {
+    let (mut rq, mut rr, rs) = (q.round(), r.round(), s.round());
+    let (dq, dr, ds) = ((rq - q).abs(), (rr - r).abs(), (rs - s).abs());
+
+    // Recompute whichever coordinate was rounded the furthest from the other two
+    if dq > dr && dq > ds {
+        rq = -rr - rs;
+    } else if dr > ds {
+        rr = -rq - rs;
+    }
+
+    Hex::new(rq as i64, rr as i64)
+}
+
+impl std::fmt::Display for Hex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.q, self.r, self.s())
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, rhs: Self) {
+        self.q += rhs.q;
+        self.r += rhs.r;
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl SubAssign for Hex {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.q -= rhs.q;
+        self.r -= rhs.r;
+    }
+}
+
+impl Mul<i64> for Hex {
+    type Output = Self;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+macro_rules! hex_dir {
+    ($dir:ident, [$($variant:ident = $axial:expr, $name:expr;)*]) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum $dir {
+            $($variant,)*
+        }
+
+        impl $dir {
+            // Clockwise, matching `index()`
+            pub const ALL: [$dir; 6] = [$($dir::$variant,)*];
+
+            pub fn index(self) -> usize {
+                self as usize
+            }
+
+            pub fn from_index(index: usize) -> Self {
+                Self::ALL[index % 6]
+            }
+
+            // Turns by 60 degrees
+            pub fn turn_right(self) -> Self {
+                Self::from_index(self.index() + 1)
+            }
+
+            // Turns by 60 degrees
+            pub fn turn_left(self) -> Self {
+                Self::from_index(self.index() + 5)
+            }
+
+            pub fn opposite(self) -> Self {
+                Self::from_index(self.index() + 3)
+            }
+
+            pub fn to_hex(self) -> Hex {
+                match self {
+                    $($dir::$variant => AXIAL_DIRECTIONS[$axial],)*
+                }
+            }
+
+            pub fn name(self) -> &'static str {
+                match self {
+                    $($dir::$variant => $name,)*
+                }
+            }
+        }
+
+        // Case insensitive, e.g. "ne" or "NE"
+        impl FromStr for $dir {
+            type Err = ParseDirError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                Self::ALL
+                    .into_iter()
+                    .find(|dir| dir.name().eq_ignore_ascii_case(s.trim()))
+                    .ok_or_else(|| ParseDirError(s.to_string()))
+            }
+        }
+
+        impl Add<$dir> for Hex {
+            type Output = Hex;
+
+            fn add(self, rhs: $dir) -> Self::Output {
+                self + rhs.to_hex()
+            }
+        }
+
+        impl AddAssign<$dir> for Hex {
+            fn add_assign(&mut self, rhs: $dir) {
+                *self += rhs.to_hex();
+            }
+        }
+    };
+}
+
+hex_dir!(FlatHexDir, [
+    N = 2, "n";
+    NE = 1, "ne";
+    SE = 0, "se";
+    S = 5, "s";
+    SW = 4, "sw";
+    NW = 3, "nw";
+]);
+
+hex_dir!(PointyHexDir, [
+    NE = 1, "ne";
+    E = 0, "e";
+    SE = 5, "se";
+    SW = 4, "sw";
+    W = 3, "w";
+    NW = 2, "nw";
+]);
+
+// An unbounded hex-keyed map, where unset hexes read as the default value
+#[derive(Debug, Clone)]
+pub struct HexMap<T: Clone> {
+    cells: HashMap<Hex, T>,
+    default: T,
+}
+
+impl<T: Clone> HexMap<T> {
+    pub fn new_with_default() -> Self
+    where
+        T: Default,
+    {
+        Self::new_with(T::default())
+    }
+
+    pub fn new_with(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+        }
+    }
+
+    pub fn get(&self, hex: Hex) -> &T {
+        self.cells.get(&hex).unwrap_or(&self.default)
+    }
+
+    pub fn get_mut(&mut self, hex: Hex) -> &mut T {
+        let default = &self.default;
+        self.cells.entry(hex).or_insert_with(|| default.clone())
+    }
+
+    pub fn contains(&self, hex: Hex) -> bool {
+        self.cells.contains_key(&hex)
+    }
+
+    pub fn remove(&mut self, hex: Hex) -> Option<T> {
+        self.cells.remove(&hex)
+    }
+
+    // Number of hexes that were written to
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // Iterates the written hexes in no particular order
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (Hex, &T)> {
+        self.cells.iter().map(|(hex, value)| (*hex, value))
+    }
+}
+
+impl<T: Clone> Index<Hex> for HexMap<T> {
+    type Output = T;
+
+    fn index(&self, index: Hex) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl<T: Clone> IndexMut<Hex> for HexMap<T> {
+    fn index_mut(&mut self, index: Hex) -> &mut Self::Output {
+        self.get_mut(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn walk(path: &str) -> Hex {
+        path.split(',')
+            .map(|d| d.parse::<FlatHexDir>().unwrap())
+            .fold(Hex::zero(), |hex, dir| hex + dir)
+    }
+
+    #[test]
+    fn test_hex_paths() {
+        assert_eq!(walk("ne,ne,ne").length(), 3);
+        assert_eq!(walk("ne,ne,sw,sw").length(), 0);
+        assert_eq!(walk("ne,ne,s,s").length(), 2);
+        assert_eq!(walk("se,sw,se,sw,sw").length(), 3);
+
+        for dir in FlatHexDir::ALL {
+            assert_eq!(dir.to_hex() + dir.opposite().to_hex(), Hex::zero());
+            assert_eq!(dir.to_hex().length(), 1);
+        }
+        assert_eq!(FlatHexDir::N.turn_right(), FlatHexDir::NE);
+        assert_eq!("W".parse::<PointyHexDir>(), Ok(PointyHexDir::W));
+        assert!("n".parse::<PointyHexDir>().is_err());
+    }
+
+    #[test]
+    fn test_rings_and_lines() {
+        let center = Hex::new(2, -1);
+        assert_eq!(center.ring(0), vec![center]);
+        for radius in 1..4 {
+            let ring = center.ring(radius);
+            assert_eq!(ring.len(), 6 * radius as usize);
+            assert!(ring.iter().all(|h| h.distance(center) == radius));
+        }
+        assert_eq!(center.spiral(2).len(), 19);
+
+        let target = Hex::new(-2, 3);
+        let line = center.line_to(target);
+        assert_eq!(line.len() as i64, center.distance(target) + 1);
+        assert_eq!(line[0], center);
+        assert_eq!(*line.last().unwrap(), target);
+        assert!(line.windows(2).all(|w| w[0].distance(w[1]) == 1));
+
+        let mut map = HexMap::new_with(0);
+        map[target] += 5;
+        assert_eq!(map[target], 5);
+        assert_eq!(map[center], 0);
+        assert_eq!(map.len(), 1);
+    }
+}
@@ -1,12 +1,20 @@
-use std::ops::*;
+use std::{cell::Cell, ops::*};
 
 use crate::{Grid2, Pos2, ToSignedIndex, Vec2};
 
+// Inclusive min and max corners, or None when empty
+pub(crate) type Bounds = Option<(Pos2<isize>, Pos2<isize>)>;
+
+#[derive(Debug, Clone)]
 pub struct IGrid2<T: Clone> {
     grid: Grid2<T>,
     up: usize,
     left: usize,
     default: T,
+    // Inclusive min and max of every cell that may hold a non-default value
+    written: Bounds,
+    // The tight bounds within `written`, computed on demand and cleared by every write
+    bounds: Cell<Option<Bounds>>,
 }
 
 fn get_dim_size(current: usize, index: isize) -> usize {
@@ -25,6 +33,25 @@ fn get_dim_size(current: usize, index: isize) -> usize {
     next_fitting_square as usize
 }
 
+pub(crate) fn include_in_bounds(bounds: Bounds, pos: Pos2<isize>) -> Bounds {
+    Some(match bounds {
+        None => (pos, pos),
+        Some((min, max)) => (
+            Pos2::new(min.x.min(pos.x), min.y.min(pos.y)),
+            Pos2::new(max.x.max(pos.x), max.y.max(pos.y)),
+        ),
+    })
+}
+
+fn iter_bounds(bounds: Bounds) -> impl Iterator<Item = Pos2<isize>> {
+    let (min, max) = bounds.unwrap_or((Pos2::new(0, 0), Pos2::new(-1, -1)));
+    (min.y..=max.y).flat_map(move |y| (min.x..=max.x).map(move |x| Pos2::new(x, y)))
+}
+
+fn signed_coord(coord: Pos2<impl ToSignedIndex + Copy>) -> Pos2<isize> {
+    coord.map(|c| c.to_index().expect("Coordinate doesn't fit in isize"))
+}
+
 impl<T: Clone> IGrid2<T> {
     pub fn new_with_default() -> Self
     where
@@ -35,6 +62,8 @@ impl<T: Clone> IGrid2<T> {
             up: 0,
             left: 0,
             default: T::default(),
+            written: None,
+            bounds: Cell::new(None),
         }
     }
 
@@ -44,9 +73,15 @@ impl<T: Clone> IGrid2<T> {
             up: 0,
             left: 0,
             default,
+            written: None,
+            bounds: Cell::new(None),
         }
     }
 
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
     pub fn up_capacity(&self) -> usize {
         self.up
     }
@@ -65,6 +100,8 @@ impl<T: Clone> IGrid2<T> {
 
     fn get_inner_grid_coord(&self, coord: Pos2<impl ToSignedIndex + Copy>) -> Option<Pos2<usize>> {
         let as_index = coord.map(|c| c.to_index());
+        // Anything beyond isize can't be inside the grid
+        let as_index = Pos2::new(as_index.x?, as_index.y?);
         if -as_index.x > self.left_capacity() as isize
             || -as_index.y > self.up_capacity() as isize
             || as_index.x >= self.right_capacity() as isize
@@ -80,10 +117,11 @@ impl<T: Clone> IGrid2<T> {
     }
 
     pub fn expand_to_fit(&mut self, coord: Pos2<impl ToSignedIndex + Copy>) {
-        let new_up = get_dim_size(self.up_capacity() + 1, -coord.y.to_index()) - 1;
-        let new_left = get_dim_size(self.left_capacity() + 1, -coord.x.to_index()) - 1;
-        let new_down = get_dim_size(self.down_capacity(), coord.y.to_index());
-        let new_right = get_dim_size(self.right_capacity(), coord.x.to_index());
+        let coord = signed_coord(coord);
+        let new_up = get_dim_size(self.up_capacity() + 1, -coord.y) - 1;
+        let new_left = get_dim_size(self.left_capacity() + 1, -coord.x) - 1;
+        let new_down = get_dim_size(self.down_capacity(), coord.y);
+        let new_right = get_dim_size(self.right_capacity(), coord.x);
 
         let new_width = new_left + new_right;
         let new_height = new_up + new_down;
@@ -120,9 +158,66 @@ impl<T: Clone> IGrid2<T> {
 
     pub fn get_mut(&mut self, coord: Pos2<impl ToSignedIndex + Copy>) -> &mut T {
         self.expand_to_fit(coord);
+        self.written = include_in_bounds(self.written, signed_coord(coord));
+        self.bounds.set(None);
         let coord = self.get_inner_grid_coord(coord).unwrap();
         &mut self.grid[coord]
     }
+
+    // Unlike writing through `get_mut`, storing the default value never grows the grid
+    pub fn set(&mut self, coord: Pos2<impl ToSignedIndex + Copy>, value: T)
+    where
+        T: PartialEq,
+    {
+        if value != self.default {
+            *self.get_mut(coord) = value;
+        } else if let Some(coord) = self.get_inner_grid_coord(coord) {
+            self.grid[coord] = value;
+            self.bounds.set(None);
+        }
+    }
+
+    // Inclusive bounding box of all cells that currently hold a non-default value
+    pub fn bounds(&self) -> Option<(Pos2<isize>, Pos2<isize>)>
+    where
+        T: PartialEq,
+    {
+        if let Some(bounds) = self.bounds.get() {
+            return bounds;
+        }
+
+        let bounds = iter_bounds(self.written)
+            .filter(|&pos| *self.get(pos) != self.default)
+            .fold(None, include_in_bounds);
+        self.bounds.set(Some(bounds));
+        bounds
+    }
+
+    // Iterates every cell within `bounds()` in reading order
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (Pos2<isize>, &T)>
+    where
+        T: PartialEq,
+    {
+        iter_bounds(self.bounds()).map(|pos| (pos, self.get(pos)))
+    }
+
+    // Crops the grid to the non-default cells. The returned offset is the position of the
+    // grid's (0, 0) cell, so `grid_pos + offset` gives back the original position.
+    pub fn to_cropped_grid(&self) -> (Grid2<T>, Vec2<isize>)
+    where
+        T: PartialEq,
+    {
+        let Some((min, max)) = self.bounds() else {
+            return (Grid2::new_empty(), Vec2::zero());
+        };
+
+        let size = (max - min).map(|c| c as usize + 1);
+        let grid = Grid2::from_fn(size.x, size.y, |pos| {
+            self.get(pos.map(|c| c as isize) + min.to_vec()).clone()
+        });
+
+        (grid, min.to_vec())
+    }
 }
 
 impl<T: Clone, I: ToSignedIndex + std::fmt::Debug + Copy + Default> Index<Pos2<I>> for IGrid2<T> {
@@ -141,6 +236,23 @@ impl<T: Clone, I: ToSignedIndex + std::fmt::Debug + Copy + Default> IndexMut<Pos
     }
 }
 
+impl<T: Clone + PartialEq + std::fmt::Display> std::fmt::Display for IGrid2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                write!(f, "{}", self.get(Pos2::new(x, y)))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -165,4 +277,71 @@ mod tests {
         assert_eq!(grid[Pos2::new(100, 100)], 2);
         assert_eq!(grid[Pos2::new(0, 0)], 1);
     }
+
+    #[test]
+    fn test_igrid2_bounds() {
+        let mut grid = IGrid2::new_with('.');
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.to_string(), "");
+
+        grid[Pos2::new(-2, 1)] = '#';
+        grid[Pos2::new(1, -1)] = '#';
+        grid[Pos2::new(3, 3)] = '.';
+
+        assert_eq!(grid.bounds(), Some((Pos2::new(-2, -1), Pos2::new(1, 1))));
+        assert_eq!(grid.iter().filter(|(_, &c)| c == '#').count(), 2);
+
+        let (cropped, offset) = grid.to_cropped_grid();
+        assert_eq!(offset, Vec2::new(-2, -1));
+        assert_eq!((cropped.width(), cropped.height()), (4, 3));
+        assert_eq!(cropped[Pos2::new(3, 0)], '#');
+        assert_eq!(cropped[Pos2::new(0, 2)], '#');
+
+        grid[Pos2::new(3, 3)] = '#';
+        assert_eq!(grid.to_string(), "...#..\n......\n#.....\n......\n.....#\n");
+    }
+
+    #[test]
+    fn test_igrid2_bounds_ignore_default() {
+        let mut grid = IGrid2::new_with('.');
+        grid[Pos2::new(0, 0)] = '#';
+        let bounds = Some((Pos2::new(0, 0), Pos2::new(0, 0)));
+
+        // Mutable access alone doesn't store anything
+        let _ = &mut grid[Pos2::new(50, -50)];
+        assert_eq!(grid.bounds(), bounds);
+        grid[Pos2::new(-20, 7)] = '.';
+        assert_eq!(grid.bounds(), bounds);
+
+        let capacity = grid.right_capacity();
+        grid.set(Pos2::new(1000, 0), '.');
+        assert_eq!(grid.right_capacity(), capacity);
+        assert_eq!(grid.bounds(), bounds);
+        assert_eq!(grid.to_string(), "#\n");
+
+        grid.set(Pos2::new(2, 1), '#');
+        assert_eq!(grid.bounds(), Some((Pos2::new(0, 0), Pos2::new(2, 1))));
+        grid.set(Pos2::new(2, 1), '.');
+        assert_eq!(grid.bounds(), bounds);
+    }
+
+    #[test]
+    fn test_igrid2_spiral_sums() {
+        // Each cell of the spiral holds the sum of its already filled neighbors
+        let mut grid = IGrid2::<u32>::new_with_default();
+        for pos in crate::UlamSpiral::default().iter().take(9) {
+            let sum = pos.iter_eight_directions().map(|n| grid[n]).sum::<u32>();
+            grid.set(pos, sum.max(1));
+        }
+
+        assert_eq!(grid.iter().map(|(_, &v)| v).max(), Some(25));
+        assert_eq!(grid.iter().next(), Some((Pos2::new(-1, -1), &5)));
+
+        let (cropped, offset) = grid.to_cropped_grid();
+        assert_eq!(offset, Vec2::new(-1, -1));
+        assert_eq!(
+            cropped,
+            Grid2::try_from(vec![vec![5, 4, 2], vec![10, 1, 1], vec![11, 23, 25]]).unwrap()
+        );
+    }
 }
@@ -0,0 +1,416 @@
+use std::ops::{Range, RangeInclusive};
+
+use crate::Integer;
+
+// Anything that describes a contiguous run of integers. Intervals are kept inclusive on both
+// ends internally so that ranges reaching T::MAX still work.
+pub trait IntervalBounds<T> {
+    // Inclusive (start, end), or None if the range is empty
+    fn to_bounds(self) -> Option<(T, T)>;
+}
+
+impl<T: Integer> IntervalBounds<T> for Range<T> {
+    fn to_bounds(self) -> Option<(T, T)> {
+        (self.start < self.end).then(|| (self.start, self.end - T::one()))
+    }
+}
+
+impl<T: Integer> IntervalBounds<T> for RangeInclusive<T> {
+    fn to_bounds(self) -> Option<(T, T)> {
+        let (start, end) = self.into_inner();
+        (start <= end).then_some((start, end))
+    }
+}
+
+impl<T: Integer> IntervalBounds<T> for &Range<T> {
+    fn to_bounds(self) -> Option<(T, T)> {
+        self.clone().to_bounds()
+    }
+}
+
+impl<T: Integer> IntervalBounds<T> for &RangeInclusive<T> {
+    fn to_bounds(self) -> Option<(T, T)> {
+        self.clone().to_bounds()
+    }
+}
+
+// Whether `end` is directly followed by `start`, without overflowing at T::MAX
+fn touches<T: Integer>(end: T, start: T) -> bool {
+    end.checked_add(T::one()).is_none_or(|next| next >= start)
+}
+
+// A set of integers, stored as sorted, disjoint and non-adjacent intervals
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<(T, T)>,
+}
+
+impl<T: Integer> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn from_range(range: impl IntervalBounds<T>) -> Self {
+        Self {
+            intervals: range.to_bounds().into_iter().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    // Number of separate intervals, not of values
+    pub fn interval_count(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.intervals.iter().map(|&(start, end)| start..=end)
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|&(start, _)| start)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.intervals.last().map(|&(_, end)| end)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|&(_, end)| end < value);
+        self.intervals
+            .get(i)
+            .is_some_and(|&(start, _)| start <= value)
+    }
+
+    // Total number of values in the set. Panics if that doesn't fit in T.
+    pub fn total_len(&self) -> T {
+        self.intervals
+            .iter()
+            .try_fold(T::zero(), |total, &(start, end)| {
+                (end.checked_sub(start)?.checked_add(T::one())?).checked_add(total)
+            })
+            .expect("Interval length overflowed")
+    }
+
+    // Adds the range, merging it with any interval it overlaps or touches
+    pub fn insert(&mut self, range: impl IntervalBounds<T>) {
+        let Some((mut start, mut end)) = range.to_bounds() else {
+            return;
+        };
+
+        let first = self.intervals.partition_point(|&(_, e)| !touches(e, start));
+        let last = self.intervals.partition_point(|&(s, _)| touches(end, s));
+        if first < last {
+            start = start.min(self.intervals[first].0);
+            end = end.max(self.intervals[last - 1].1);
+        }
+        self.intervals.splice(first..last, [(start, end)]);
+    }
+
+    // Removes the range, splitting any interval it cuts through
+    pub fn remove(&mut self, range: impl IntervalBounds<T>) {
+        let Some((start, end)) = range.to_bounds() else {
+            return;
+        };
+
+        let first = self.intervals.partition_point(|&(_, e)| e < start);
+        let last = self.intervals.partition_point(|&(s, _)| s <= end);
+        if first >= last {
+            return;
+        }
+
+        let (first_start, _) = self.intervals[first];
+        let (_, last_end) = self.intervals[last - 1];
+        let left = (first_start < start).then(|| (first_start, start - T::one()));
+        let right = (last_end > end).then(|| (end + T::one(), last_end));
+        self.intervals
+            .splice(first..last, left.into_iter().chain(right));
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for &(start, end) in &other.intervals {
+            result.insert(start..=end);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(a_start, a_end)), Some(&(b_start, b_end))) =
+            (self.intervals.get(i), other.intervals.get(j))
+        {
+            let (start, end) = (a_start.max(b_start), a_end.min(b_end));
+            if start <= end {
+                intervals.push((start, end));
+            }
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { intervals }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for &(start, end) in &other.intervals {
+            result.remove(start..=end);
+        }
+        result
+    }
+
+    // Every value of T that isn't in the set
+    pub fn complement(&self) -> Self {
+        let mut intervals = Vec::new();
+        let mut next = Some(T::MIN);
+        for &(start, end) in &self.intervals {
+            let Some(gap_start) = next else { break };
+            if gap_start < start {
+                intervals.push((gap_start, start - T::one()));
+            }
+            next = end.checked_add(T::one());
+        }
+        if let Some(gap_start) = next {
+            intervals.push((gap_start, T::MAX));
+        }
+        Self { intervals }
+    }
+}
+
+impl<T: Integer, R: IntervalBounds<T>> FromIterator<R> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = R>>(iter: I) -> Self {
+        let mut result = Self::new();
+        for range in iter {
+            result.insert(range);
+        }
+        result
+    }
+}
+
+impl<T: Integer, R: IntervalBounds<T>> Extend<R> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = R>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+// Maps disjoint intervals to values. Inserting over part of an existing interval splits it, so
+// the part that wasn't overwritten keeps its old value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalMap<T, V> {
+    entries: Vec<(T, T, V)>,
+}
+
+impl<T: Integer, V: Clone> Default for IntervalMap<T, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Integer, V: Clone> IntervalMap<T, V> {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (RangeInclusive<T>, &V)> + '_ {
+        self.entries
+            .iter()
+            .map(|(start, end, value)| (*start..=*end, value))
+    }
+
+    pub fn get(&self, key: T) -> Option<&V> {
+        let i = self.entries.partition_point(|&(_, end, _)| end < key);
+        self.entries
+            .get(i)
+            .filter(|&&(start, _, _)| start <= key)
+            .map(|(_, _, value)| value)
+    }
+
+    // The set of keys that have a value
+    pub fn keys(&self) -> IntervalSet<T> {
+        self.entries
+            .iter()
+            .map(|&(start, end, _)| start..=end)
+            .collect()
+    }
+
+    // Sets the value for the whole range, overwriting whatever was there
+    pub fn insert(&mut self, range: impl IntervalBounds<T>, value: V) {
+        let Some((start, end)) = range.to_bounds() else {
+            return;
+        };
+        let i = self.remove_bounds(start, end);
+        self.entries.insert(i, (start, end, value));
+    }
+
+    pub fn remove(&mut self, range: impl IntervalBounds<T>) {
+        if let Some((start, end)) = range.to_bounds() {
+            self.remove_bounds(start, end);
+        }
+    }
+
+    // Returns the index where an entry for start..=end would now go
+    fn remove_bounds(&mut self, start: T, end: T) -> usize {
+        let first = self.entries.partition_point(|&(_, e, _)| e < start);
+        let last = self.entries.partition_point(|&(s, _, _)| s <= end);
+        if first >= last {
+            return first;
+        }
+
+        let (first_start, _, ref first_value) = self.entries[first];
+        let (_, last_end, ref last_value) = self.entries[last - 1];
+        let left =
+            (first_start < start).then(|| (first_start, start - T::one(), first_value.clone()));
+        let right = (last_end > end).then(|| (end + T::one(), last_end, last_value.clone()));
+        let kept_left = usize::from(left.is_some());
+        self.entries
+            .splice(first..last, left.into_iter().chain(right));
+        first + kept_left
+    }
+
+    // Cuts the range into the pieces covered by each entry, and the gaps in between with None
+    pub fn split(&self, range: impl IntervalBounds<T>) -> Vec<(RangeInclusive<T>, Option<&V>)> {
+        let Some((start, end)) = range.to_bounds() else {
+            return Vec::new();
+        };
+
+        let mut pieces = Vec::new();
+        let mut next = Some(start);
+        let first = self.entries.partition_point(|&(_, e, _)| e < start);
+        for (entry_start, entry_end, value) in &self.entries[first..] {
+            let Some(piece_start) = next.filter(|&s| s <= end) else {
+                break;
+            };
+            if *entry_start > end {
+                break;
+            }
+            if piece_start < *entry_start {
+                pieces.push((piece_start..=*entry_start - T::one(), None));
+            }
+            let piece_end = end.min(*entry_end);
+            pieces.push((piece_start.max(*entry_start)..=piece_end, Some(value)));
+            next = piece_end.checked_add(T::one());
+        }
+        if let Some(piece_start) = next.filter(|&s| s <= end) {
+            pieces.push((piece_start..=end, None));
+        }
+        pieces
+    }
+}
+
+impl<T: Integer, V: Clone, R: IntervalBounds<T>> FromIterator<(R, V)> for IntervalMap<T, V> {
+    fn from_iter<I: IntoIterator<Item = (R, V)>>(iter: I) -> Self {
+        let mut result = Self::new();
+        for (range, value) in iter {
+            result.insert(range, value);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval_set() {
+        let mut set = [1..5, 10..15, 5..7]
+            .into_iter()
+            .collect::<IntervalSet<i32>>();
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=6, 10..=14]);
+        assert_eq!(set.total_len(), 11);
+        assert!(set.contains(6) && !set.contains(7) && set.contains(10));
+
+        set.insert(7..=9);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=14]);
+        set.remove(3..=4);
+        set.remove(12..100);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..=2, 5..=11]);
+
+        let other = [0..=1, 4..=6, 11..=20]
+            .into_iter()
+            .collect::<IntervalSet<i32>>();
+        assert_eq!(
+            set.intersection(&other).iter().collect::<Vec<_>>(),
+            vec![1..=1, 5..=6, 11..=11]
+        );
+        assert_eq!(
+            set.union(&other).iter().collect::<Vec<_>>(),
+            vec![0..=2, 4..=20]
+        );
+        assert_eq!(
+            set.difference(&other).iter().collect::<Vec<_>>(),
+            vec![2..=2, 7..=10]
+        );
+        assert_eq!(
+            set.complement().iter().collect::<Vec<_>>(),
+            vec![i32::MIN..=0, 3..=4, 12..=i32::MAX]
+        );
+        assert_eq!(set.complement().complement(), set);
+
+        let full = IntervalSet::from_range(0..=u8::MAX);
+        assert!(full.complement().is_empty());
+        assert_eq!(IntervalSet::<u8>::new().complement(), full);
+        let mut edge = [250..=u8::MAX].into_iter().collect::<IntervalSet<u8>>();
+        edge.insert(0..250);
+        assert_eq!(edge, full);
+    }
+
+    #[test]
+    fn test_interval_map() {
+        let mut map = IntervalMap::new();
+        map.insert(0..10, 'a');
+        map.insert(20..=29, 'b');
+        map.insert(5..25, 'c');
+        assert_eq!(
+            map.iter().collect::<Vec<_>>(),
+            vec![(0..=4, &'a'), (5..=24, &'c'), (25..=29, &'b')]
+        );
+        map.insert(10..12, 'd');
+        assert_eq!(map.len(), 5);
+        assert_eq!(map.get(11), Some(&'d'));
+        assert_eq!(map.get(12), Some(&'c'));
+        assert_eq!(map.get(30), None);
+
+        map.remove(2..=3);
+        assert_eq!(
+            map.split(-2..=6),
+            vec![
+                (-2..=-1, None),
+                (0..=1, Some(&'a')),
+                (2..=3, None),
+                (4..=4, Some(&'a')),
+                (5..=6, Some(&'c')),
+            ]
+        );
+        assert_eq!(
+            map.split(26..40),
+            vec![(26..=29, Some(&'b')), (30..=39, None)]
+        );
+        assert_eq!(map.keys().iter().collect::<Vec<_>>(), vec![0..=1, 4..=29]);
+    }
+}
@@ -1,18 +1,61 @@
+mod automaton;
+mod compress;
 mod coord2;
+mod coord3;
+mod dir;
 mod grid2;
+mod grid3;
+mod hex;
 mod igrid2;
+mod interval;
 mod iter_helpers;
+mod line;
+mod linear_system;
+mod matrix;
+mod modint;
+mod number_theory;
 mod nums;
 mod parse;
+mod polygon;
+mod polynomial;
+mod primes;
+mod quadratic;
+mod rational;
+mod rect;
+mod slide;
+mod sparse_grid2;
+mod spiral;
 mod string_helpers;
+mod symmetry;
 
+pub use automaton::*;
+pub use compress::*;
 pub use coord2::*;
+pub use coord3::*;
+pub use dir::*;
 pub use grid2::*;
+pub use grid3::*;
+pub use hex::*;
 pub use igrid2::*;
+pub use interval::*;
 pub use iter_helpers::*;
+pub use line::*;
+pub use linear_system::*;
+pub use matrix::*;
+pub use modint::*;
+pub use number_theory::*;
 pub use nums::*;
 pub use parse::*;
+pub use polygon::*;
+pub use polynomial::*;
+pub use primes::*;
+pub use quadratic::*;
+pub use rational::*;
+pub use rect::*;
+pub use sparse_grid2::*;
+pub use spiral::*;
 pub use string_helpers::*;
+pub use symmetry::*;
 
 #[macro_export]
 macro_rules! day_input {
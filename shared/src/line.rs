@@ -0,0 +1,382 @@
+use std::ops::*;
+
+use crate::{abs_diff, gcd, Dir8, Grid2, One, Pos2, ToSignedIndex, ToUnsignedIndex, Vec2};
+
+// Iterates every integer point on the line from `from` to `to`, both included, using
+// Bresenham's algorithm. Horizontal, vertical and 45 degree lines are exact.
+pub struct LineIter<T> {
+    current: Pos2<T>,
+    end: Pos2<T>,
+    delta: Vec2<T>,
+    step: Vec2<T>,
+    err: T,
+    done: bool,
+}
+
+pub fn iter_line<T>(from: Pos2<T>, to: Pos2<T>) -> LineIter<T>
+where
+    T: Copy + Default + PartialOrd + One + Neg<Output = T> + Add<Output = T> + Sub<Output = T>,
+{
+    let sign = |a: T, b: T| if a < b { T::one() } else { -T::one() };
+
+    // dy is kept negative so that a single error term covers every octant
+    let delta = Vec2::new(abs_diff(from.x, to.x), -abs_diff(from.y, to.y));
+    LineIter {
+        current: from,
+        end: to,
+        delta,
+        step: Vec2::new(sign(from.x, to.x), sign(from.y, to.y)),
+        err: delta.x + delta.y,
+        done: false,
+    }
+}
+
+impl<T> Iterator for LineIter<T>
+where
+    T: Copy + Default + PartialOrd + One + Neg<Output = T> + Add<Output = T> + Sub<Output = T>,
+{
+    type Item = Pos2<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.current;
+        if self.current == self.end {
+            self.done = true;
+            return Some(result);
+        }
+
+        let e2 = self.err + self.err;
+        if e2 >= self.delta.y {
+            self.err = self.err + self.delta.y;
+            self.current.x = self.current.x + self.step.x;
+        }
+        if e2 <= self.delta.x {
+            self.err = self.err + self.delta.x;
+            self.current.y = self.current.y + self.step.y;
+        }
+
+        Some(result)
+    }
+}
+
+impl<T> Grid2<T> {
+    // Walks from `start` in the given direction until leaving the grid. The start itself isn't
+    // included, so this gives exactly the cells visible from it along that direction.
+    pub fn iter_ray<'a, I>(&self, start: Pos2<I>, dir: Dir8) -> impl 'a + Iterator<Item = Pos2<I>>
+    where
+        I: 'a + Copy + ToSignedIndex + TryFrom<usize>,
+    {
+        // Walk in isize so that unsigned positions can step below zero and stop there
+        let (width, height) = (self.width(), self.height());
+        let step = dir.to_vec::<isize>();
+        let start = ToSignedIndex::to_index(start.x).zip(ToSignedIndex::to_index(start.y));
+
+        std::iter::successors(start.map(|(x, y)| Pos2::new(x, y) + step), move |&pos| {
+            Some(pos + step)
+        })
+        .map_while(move |pos| {
+            let x = ToUnsignedIndex::to_index(pos.x)?;
+            let y = ToUnsignedIndex::to_index(pos.y)?;
+            if x >= width || y >= height {
+                return None;
+            }
+            Some(Pos2::new(I::try_from(x).ok()?, I::try_from(y).ok()?))
+        })
+    }
+}
+
+// A point with exact rational coordinates `(x / den, y / den)`, where `den` is positive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FracPos2 {
+    pub x: i128,
+    pub y: i128,
+    pub den: i128,
+}
+
+impl FracPos2 {
+    pub fn to_pos(self) -> Option<Pos2<i64>> {
+        if self.x % self.den != 0 || self.y % self.den != 0 {
+            return None;
+        }
+
+        Some(Pos2::new(
+            (self.x / self.den) as i64,
+            (self.y / self.den) as i64,
+        ))
+    }
+
+    pub fn to_f64(self) -> (f64, f64) {
+        (
+            self.x as f64 / self.den as f64,
+            self.y as f64 / self.den as f64,
+        )
+    }
+}
+
+impl From<Pos2<i64>> for FracPos2 {
+    fn from(pos: Pos2<i64>) -> Self {
+        FracPos2 {
+            x: pos.x as i128,
+            y: pos.y as i128,
+            den: 1,
+        }
+    }
+}
+
+// A segment from `origin` to `origin + dir`, or a ray from `origin` along `dir`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Line2 {
+    pub origin: Pos2<i64>,
+    pub dir: Vec2<i64>,
+    pub bounded: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineIntersection {
+    None,
+    Point(FracPos2),
+    // Collinear overlap between the two inclusive endpoints
+    Segment(Pos2<i64>, Pos2<i64>),
+    // Collinear overlap that continues forever along the direction
+    Ray(Pos2<i64>, Vec2<i64>),
+}
+
+fn cross(a: Vec2<i128>, b: Vec2<i128>) -> i128 {
+    a.x * b.y - a.y * b.x
+}
+
+fn dot(a: Vec2<i128>, b: Vec2<i128>) -> i128 {
+    a.x * b.x + a.y * b.y
+}
+
+fn wide(v: Vec2<i64>) -> Vec2<i128> {
+    v.map(|c| c as i128)
+}
+
+impl Line2 {
+    pub fn segment(from: Pos2<i64>, to: Pos2<i64>) -> Self {
+        Line2 {
+            origin: from,
+            dir: to - from,
+            bounded: true,
+        }
+    }
+
+    pub fn ray(origin: Pos2<i64>, dir: Vec2<i64>) -> Self {
+        Line2 {
+            origin,
+            dir,
+            bounded: false,
+        }
+    }
+
+    pub fn contains(&self, pos: Pos2<i64>) -> bool {
+        let offset = wide(pos - self.origin);
+        let dir = wide(self.dir);
+        if cross(offset, dir) != 0 {
+            return false;
+        }
+
+        let t = dot(offset, dir);
+        if dir == Vec2::zero() {
+            offset == Vec2::zero()
+        } else {
+            t >= 0 && (!self.bounded || t <= dot(dir, dir))
+        }
+    }
+
+    pub fn intersect(&self, other: &Line2) -> LineIntersection {
+        // Degenerate zero length segments are just points
+        if self.dir == Vec2::zero() || other.dir == Vec2::zero() {
+            let (point, line) = if self.dir == Vec2::zero() {
+                (self.origin, other)
+            } else {
+                (other.origin, self)
+            };
+
+            return if line.contains(point) {
+                LineIntersection::Point(point.into())
+            } else {
+                LineIntersection::None
+            };
+        }
+
+        let (r, s) = (wide(self.dir), wide(other.dir));
+        let qp = wide(other.origin - self.origin);
+        let denom = cross(r, s);
+
+        if denom == 0 {
+            if cross(qp, r) != 0 {
+                return LineIntersection::None;
+            }
+            return self.collinear_overlap(other);
+        }
+
+        // Solve self.origin + r * t == other.origin + s * u, with t and u kept as fractions
+        let (mut t_num, mut u_num, mut den) = (cross(qp, s), cross(qp, r), denom);
+        if den < 0 {
+            (t_num, u_num, den) = (-t_num, -u_num, -den);
+        }
+
+        let in_range = |num: i128, bounded: bool| num >= 0 && (!bounded || num <= den);
+        if !in_range(t_num, self.bounded) || !in_range(u_num, other.bounded) {
+            return LineIntersection::None;
+        }
+
+        let origin = wide(self.origin.to_vec()) * den;
+        let point = origin + r * t_num;
+        LineIntersection::Point(reduce(FracPos2 {
+            x: point.x,
+            y: point.y,
+            den,
+        }))
+    }
+
+    // Both lines are known to lie on the same infinite line here. Positions are measured as
+    // `(pos - self.origin) . self.dir`, which is proportional to the distance along self.
+    fn collinear_overlap(&self, other: &Line2) -> LineIntersection {
+        let r = wide(self.dir);
+        let rr = dot(r, r);
+        let measure = |pos: Pos2<i64>| dot(wide(pos - self.origin), r);
+
+        let self_range = (Some(0), if self.bounded { Some(rr) } else { None });
+
+        let start = measure(other.origin);
+        let along = dot(wide(other.dir), r);
+        let other_range = if other.bounded {
+            let end = start + along;
+            (Some(start.min(end)), Some(start.max(end)))
+        } else if along > 0 {
+            (Some(start), None)
+        } else {
+            (None, Some(start))
+        };
+
+        let lo = match (self_range.0, other_range.0) {
+            (Some(a), Some(b)) => a.max(b),
+            (a, b) => a.or(b).unwrap(),
+        };
+        let hi = match (self_range.1, other_range.1) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        // Overlap bounds always come from original endpoints, so they map back to integers
+        let at = |m: i128| {
+            let offset = r * m / rr;
+            self.origin + offset.map(|c| c as i64)
+        };
+
+        match hi {
+            Some(hi) if lo > hi => LineIntersection::None,
+            Some(hi) if lo == hi => LineIntersection::Point(at(lo).into()),
+            Some(hi) => LineIntersection::Segment(at(lo), at(hi)),
+            None => LineIntersection::Ray(at(lo), self.dir),
+        }
+    }
+}
+
+fn reduce(pos: FracPos2) -> FracPos2 {
+    let g = gcd(gcd(pos.x, pos.y), pos.den);
+    FracPos2 {
+        x: pos.x / g,
+        y: pos.y / g,
+        den: pos.den / g,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_iter_line() {
+        let line = iter_line(Pos2::new(0, 0), Pos2::new(4, 2)).collect::<Vec<_>>();
+        assert_eq!(
+            line,
+            vec![
+                Pos2::new(0, 0),
+                Pos2::new(1, 1),
+                Pos2::new(2, 1),
+                Pos2::new(3, 2),
+                Pos2::new(4, 2)
+            ]
+        );
+
+        let diagonal = iter_line(Pos2::new(3, -3), Pos2::new(0, 0)).collect::<Vec<_>>();
+        assert_eq!(diagonal.len(), 4);
+        assert!(diagonal.iter().all(|p| p.x == -p.y));
+
+        assert_eq!(iter_line(Pos2::new(5, 5), Pos2::new(5, 5)).count(), 1);
+        assert_eq!(iter_line(Pos2::new(2, 9), Pos2::new(2, 0)).count(), 10);
+    }
+
+    #[test]
+    fn test_iter_ray() {
+        let grid = Grid2::new_with(5, 3, '.');
+        let ray = grid
+            .iter_ray(Pos2::new(1, 1), Dir8::UpRight)
+            .collect::<Vec<_>>();
+        assert_eq!(ray, vec![Pos2::new(2, 0)]);
+        assert_eq!(grid.iter_ray(Pos2::new(1, 1), Dir8::Right).count(), 3);
+        assert_eq!(grid.iter_ray(Pos2::new(0, 0), Dir8::Left).count(), 0);
+
+        let start = Pos2::<usize>::new(2, 2);
+        let ray = grid.iter_ray(start, Dir8::UpLeft).collect::<Vec<_>>();
+        assert_eq!(ray, vec![Pos2::new(1, 1), Pos2::new(0, 0)]);
+        assert_eq!(grid[ray[0]], '.');
+        assert_eq!(grid.iter_ray(start, Dir8::Down).count(), 0);
+    }
+
+    #[test]
+    fn test_intersections() {
+        let a = Line2::segment(Pos2::new(0, 0), Pos2::new(4, 4));
+        let b = Line2::segment(Pos2::new(0, 4), Pos2::new(4, 0));
+        assert_eq!(
+            a.intersect(&b),
+            LineIntersection::Point(Pos2::new(2, 2).into())
+        );
+
+        let c = Line2::segment(Pos2::new(0, 1), Pos2::new(1, 0));
+        let LineIntersection::Point(p) = a.intersect(&c) else {
+            panic!()
+        };
+        assert_eq!((p.x, p.y, p.den), (1, 1, 2));
+        assert_eq!(p.to_pos(), None);
+
+        let parallel = Line2::segment(Pos2::new(1, 0), Pos2::new(5, 4));
+        assert_eq!(a.intersect(&parallel), LineIntersection::None);
+
+        let overlapping = Line2::segment(Pos2::new(6, 6), Pos2::new(2, 2));
+        assert_eq!(
+            a.intersect(&overlapping),
+            LineIntersection::Segment(Pos2::new(2, 2), Pos2::new(4, 4))
+        );
+
+        let touching = Line2::segment(Pos2::new(4, 4), Pos2::new(9, 9));
+        assert_eq!(
+            a.intersect(&touching),
+            LineIntersection::Point(Pos2::new(4, 4).into())
+        );
+
+        let ray = Line2::ray(Pos2::new(-1, -1), Vec2::new(1, 1));
+        assert_eq!(
+            ray.intersect(&Line2::ray(Pos2::new(3, 3), Vec2::new(2, 2))),
+            LineIntersection::Ray(Pos2::new(3, 3), Vec2::new(1, 1))
+        );
+        assert_eq!(
+            ray.intersect(&Line2::ray(Pos2::new(-2, -2), Vec2::new(-1, -1))),
+            LineIntersection::None
+        );
+
+        let short = Line2::segment(Pos2::new(0, 4), Pos2::new(1, 3));
+        assert_eq!(a.intersect(&short), LineIntersection::None);
+        assert_eq!(
+            Line2::ray(Pos2::new(0, 4), Vec2::new(1, -1)).intersect(&a),
+            LineIntersection::Point(Pos2::new(2, 2).into())
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{Integer, Rational, Signed, Zero};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinearSolution<T = i128> {
+    None,
+    Unique(Vec<Rational<T>>),
+    // Every solution is `particular` plus any linear combination of the `basis` vectors
+    Infinite {
+        particular: Vec<Rational<T>>,
+        basis: Vec<Vec<Rational<T>>>,
+    },
+}
+
+// Solves `matrix * x == rhs` exactly with Gauss-Jordan elimination. Each row of `matrix` is one
+// equation, and every row must have the same number of columns (one per unknown).
+pub fn solve_linear_system<T: Integer + Signed>(
+    matrix: &[Vec<Rational<T>>],
+    rhs: &[Rational<T>],
+) -> LinearSolution<T> {
+    assert_eq!(
+        matrix.len(),
+        rhs.len(),
+        "Need one right hand side per equation"
+    );
+    let columns = matrix.first().map_or(0, |row| row.len());
+    assert!(
+        matrix.iter().all(|row| row.len() == columns),
+        "All equations need the same number of unknowns"
+    );
+
+    // Augmented matrix, with the right hand side as the last column
+    let mut rows = matrix
+        .iter()
+        .zip(rhs)
+        .map(|(row, &b)| {
+            let mut row = row.clone();
+            row.push(b);
+            row
+        })
+        .collect::<Vec<_>>();
+
+    // Reduce to row echelon form, remembering which column each pivot row belongs to
+    let mut pivots = Vec::new();
+    for col in 0..columns {
+        let r = pivots.len();
+        let Some(pivot) = (r..rows.len()).find(|&i| !rows[i][col].is_zero()) else {
+            continue;
+        };
+        rows.swap(r, pivot);
+
+        let scale = rows[r][col];
+        for value in rows[r].iter_mut() {
+            *value /= scale;
+        }
+
+        let pivot_row = rows[r].clone();
+        for (i, row) in rows.iter_mut().enumerate() {
+            let factor = row[col];
+            if i != r && !factor.is_zero() {
+                for (value, &p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                    *value -= factor * p;
+                }
+            }
+        }
+
+        pivots.push(col);
+    }
+
+    // A row of zeros that has to equal something non-zero
+    if rows[pivots.len()..]
+        .iter()
+        .any(|row| !row[columns].is_zero())
+    {
+        return LinearSolution::None;
+    }
+
+    let mut particular = vec![Rational::zero(); columns];
+    for (r, &col) in pivots.iter().enumerate() {
+        particular[col] = rows[r][columns];
+    }
+
+    // Each free variable set to 1 gives one basis vector of the solutions to matrix * x == 0
+    let basis = (0..columns)
+        .filter(|col| !pivots.contains(col))
+        .map(|free| {
+            let mut vector = vec![Rational::zero(); columns];
+            vector[free] = Rational::from_integer(T::one());
+            for (r, &col) in pivots.iter().enumerate() {
+                vector[col] = -rows[r][free];
+            }
+            vector
+        })
+        .collect::<Vec<_>>();
+
+    if basis.is_empty() {
+        LinearSolution::Unique(particular)
+    } else {
+        LinearSolution::Infinite { particular, basis }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn system(rows: &[&[i128]]) -> (Vec<Vec<Rational>>, Vec<Rational>) {
+        let matrix = rows
+            .iter()
+            .map(|row| row[..row.len() - 1].iter().map(|&v| v.into()).collect())
+            .collect();
+        let rhs = rows.iter().map(|row| row[row.len() - 1].into()).collect();
+        (matrix, rhs)
+    }
+
+    #[test]
+    fn test_solve_linear_system() {
+        let (m, b) = system(&[&[2, 1, -1, 8], &[-3, -1, 2, -11], &[-2, 1, 2, -3]]);
+        assert_eq!(
+            solve_linear_system(&m, &b),
+            LinearSolution::Unique(vec![2.into(), 3.into(), (-1).into()])
+        );
+
+        let (m, b) = system(&[&[1, 2, 1], &[3, 0, 2]]);
+        assert_eq!(
+            solve_linear_system(&m, &b),
+            LinearSolution::Unique(vec![Rational::new(2, 3), Rational::new(1, 6)])
+        );
+
+        let (m, b) = system(&[&[1, 1, 2], &[2, 2, 5]]);
+        assert_eq!(solve_linear_system(&m, &b), LinearSolution::None);
+
+        let (m, b) = system(&[&[1, 1, 1, 6], &[0, 1, -1, 1]]);
+        let LinearSolution::Infinite { particular, basis } = solve_linear_system(&m, &b) else {
+            panic!("Expected infinitely many solutions");
+        };
+        assert_eq!(basis.len(), 1);
+        for t in -3..3 {
+            let x = (0..3)
+                .map(|i| particular[i] + basis[0][i] * Rational::from(t))
+                .collect::<Vec<_>>();
+            for (row, &rhs) in m.iter().zip(&b) {
+                let lhs = row.iter().zip(&x).map(|(&a, &x)| a * x).sum::<Rational>();
+                assert_eq!(lhs, rhs);
+            }
+        }
+    }
+}
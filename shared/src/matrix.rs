@@ -0,0 +1,201 @@
+use std::ops::*;
+
+use crate::{One, Zero};
+
+// A dense matrix stored row by row. Elements only need to add and multiply, so besides plain
+// integers this works with ModInt for modular exponentiation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    data: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Matrix<T> {
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let mut data = Vec::with_capacity(rows * cols);
+        for row in 0..rows {
+            for col in 0..cols {
+                data.push(f(row, col));
+            }
+        }
+        Self { data, rows, cols }
+    }
+
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let cols = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == cols),
+            "All rows need the same length"
+        );
+        Self {
+            rows: rows.len(),
+            cols,
+            data: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.data[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        // Not `chunks`, which can't yield the empty rows of a matrix without columns
+        (0..self.rows).map(|row| self.row(row))
+    }
+
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.cols, self.rows, |row, col| self[(col, row)].clone())
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Matrix<U> {
+        Matrix {
+            data: self.data.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T: Copy + Zero + One + Add<Output = T> + Mul<Output = T>> Matrix<T> {
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        Self::from_fn(rows, cols, |_, _| T::zero())
+    }
+
+    pub fn identity(size: usize) -> Self {
+        let mut result = Self::zero(size, size);
+        for i in 0..size {
+            result[(i, i)] = T::one();
+        }
+        result
+    }
+
+    // The matrix times a column vector
+    pub fn apply(&self, vector: &[T]) -> Vec<T> {
+        assert_eq!(vector.len(), self.cols, "Vector length doesn't match");
+        self.iter_rows()
+            .map(|row| {
+                row.iter()
+                    .zip(vector)
+                    .fold(T::zero(), |acc, (&a, &b)| acc + a * b)
+            })
+            .collect()
+    }
+
+    // Exponentiation by squaring, so huge step counts only take log(exp) multiplications
+    pub fn pow(&self, mut exp: u64) -> Self {
+        assert!(self.is_square(), "Only square matrices have powers");
+        let mut result = Self::identity(self.rows);
+        let mut base = self.clone();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = &result * &base;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+}
+
+impl<T> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    // Indexed by (row, col)
+    fn index(&self, (row, col): (usize, usize)) -> &Self::Output {
+        assert!(
+            row < self.rows && col < self.cols,
+            "{row},{col} out of bounds"
+        );
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            row < self.rows && col < self.cols,
+            "{row},{col} out of bounds"
+        );
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+impl<T: Copy + Zero + One + Add<Output = T> + Mul<Output = T>> Mul for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        assert_eq!(self.cols, rhs.rows, "Matrix dimensions don't match");
+        let mut result = Matrix::zero(self.rows, rhs.cols);
+        for row in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(row, k)];
+                for col in 0..rhs.cols {
+                    result[(row, col)] = result[(row, col)] + a * rhs[(k, col)];
+                }
+            }
+        }
+        result
+    }
+}
+
+impl<T: Copy + Zero + One + Add<Output = T> + Mul<Output = T>> Mul for Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        &self * &rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ModInt1e9_7;
+
+    #[test]
+    fn test_matrix() {
+        let a = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        let b = a.transpose();
+        assert_eq!((b.rows(), b.cols()), (3, 2));
+        assert_eq!(&a * &b, Matrix::from_rows(vec![vec![14, 32], vec![32, 77]]));
+        assert_eq!(a.apply(&[1, 0, -1]), vec![-2, -2]);
+        assert_eq!(&Matrix::identity(2) * &a, a);
+        assert_eq!(Matrix::<i32>::zero(3, 0).apply(&[]), vec![0, 0, 0]);
+
+        // Walks of length 4 from each node to each node in a triangle
+        let triangle = Matrix::from_fn(3, 3, |r, c| u64::from(r != c));
+        assert_eq!(triangle.pow(4)[(0, 0)], 6);
+        assert_eq!(triangle.pow(4)[(0, 1)], 5);
+        assert_eq!(triangle.pow(0), Matrix::identity(3));
+    }
+
+    #[test]
+    fn test_fibonacci() {
+        let step = Matrix::from_rows(vec![vec![1u64, 1], vec![1, 0]]);
+        assert_eq!(step.pow(90).apply(&[1, 0])[1], 2_880_067_194_370_816_120);
+
+        // F(2n) = F(n) * (2 * F(n + 1) - F(n)), checked far beyond what u64 could hold
+        let step = step.map(|&v| ModInt1e9_7::from(v));
+        let fib = |n| step.pow(n)[(0, 1)];
+        let n = 10u64.pow(17);
+        let two = ModInt1e9_7::from(2);
+        assert_eq!(fib(2 * n), fib(n) * (two * fib(n + 1) - fib(n)));
+    }
+}
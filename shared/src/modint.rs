@@ -0,0 +1,321 @@
+use std::{iter::*, ops::*};
+
+use crate::{mod_inverse, One, Zero};
+
+// An integer modulo M, always kept in 0..M. Division multiplies by the modular inverse, which
+// panics when it doesn't exist, e.g. when dividing by a multiple of a non-prime M.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ModInt<const M: u64> {
+    value: u64,
+}
+
+// Like ModInt, for when the modulus is only known at runtime. Mixing values with different
+// moduli panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DynModInt {
+    value: u64,
+    modulus: u64,
+}
+
+pub type ModInt1e9_7 = ModInt<1_000_000_007>;
+pub type ModInt998244353 = ModInt<998_244_353>;
+
+pub(crate) fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+pub(crate) fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+fn inv_mod(a: u64, m: u64) -> Option<u64> {
+    mod_inverse(a as i128, m as i128).map(|x| x as u64)
+}
+
+impl<const M: u64> ModInt<M> {
+    pub fn new(value: u64) -> Self {
+        assert!(M > 0, "Modulus must be non-zero");
+        Self { value: value % M }
+    }
+
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    pub fn modulus(self) -> u64 {
+        M
+    }
+
+    pub fn pow(self, exp: u64) -> Self {
+        Self::new(pow_mod(self.value, exp, M))
+    }
+
+    // None if the value and M aren't coprime
+    pub fn inv(self) -> Option<Self> {
+        inv_mod(self.value, M).map(Self::new)
+    }
+
+    fn with_value(self, value: u64) -> Self {
+        Self { value }
+    }
+}
+
+impl DynModInt {
+    pub fn new(value: u64, modulus: u64) -> Self {
+        assert!(modulus > 0, "Modulus must be non-zero");
+        Self {
+            value: value % modulus,
+            modulus,
+        }
+    }
+
+    // Wraps a signed value into 0..modulus
+    pub fn from_signed(value: i128, modulus: u64) -> Self {
+        Self::new(value.rem_euclid(modulus as i128) as u64, modulus)
+    }
+
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    pub fn modulus(self) -> u64 {
+        self.modulus
+    }
+
+    pub fn zero(modulus: u64) -> Self {
+        Self::new(0, modulus)
+    }
+
+    pub fn one(modulus: u64) -> Self {
+        Self::new(1, modulus)
+    }
+
+    pub fn pow(self, exp: u64) -> Self {
+        self.with_value(pow_mod(self.value, exp, self.modulus))
+    }
+
+    // None if the value and modulus aren't coprime
+    pub fn inv(self) -> Option<Self> {
+        inv_mod(self.value, self.modulus).map(|v| self.with_value(v))
+    }
+
+    fn with_value(self, value: u64) -> Self {
+        Self {
+            value,
+            modulus: self.modulus,
+        }
+    }
+
+    fn check_modulus(self, other: Self) -> u64 {
+        assert_eq!(self.modulus, other.modulus, "Mismatched moduli");
+        self.modulus
+    }
+}
+
+macro_rules! impl_mod_ops {
+    ([$($generics:tt)*] $t:ty, $modulus:expr) => {
+        impl<$($generics)*> Add for $t {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                let m = $modulus(self, rhs);
+                let sum = self.value as u128 + rhs.value as u128;
+                self.with_value((sum % m as u128) as u64)
+            }
+        }
+
+        impl<$($generics)*> Sub for $t {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                let m = $modulus(self, rhs);
+                let diff = self.value as u128 + m as u128 - rhs.value as u128;
+                self.with_value((diff % m as u128) as u64)
+            }
+        }
+
+        impl<$($generics)*> Mul for $t {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                let m = $modulus(self, rhs);
+                self.with_value(mul_mod(self.value, rhs.value, m))
+            }
+        }
+
+        impl<$($generics)*> Div for $t {
+            type Output = Self;
+
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn div(self, rhs: Self) -> Self::Output {
+                self * rhs.inv().expect("Divisor has no modular inverse")
+            }
+        }
+
+        impl<$($generics)*> Neg for $t {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                let m = $modulus(self, self);
+                self.with_value((m - self.value) % m)
+            }
+        }
+
+        impl<$($generics)*> AddAssign for $t {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<$($generics)*> SubAssign for $t {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<$($generics)*> MulAssign for $t {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<$($generics)*> DivAssign for $t {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl<$($generics)*> std::fmt::Display for $t {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.value)
+            }
+        }
+
+        impl<$($generics)*> From<$t> for u64 {
+            fn from(value: $t) -> Self {
+                value.value
+            }
+        }
+    };
+}
+
+impl_mod_ops!([const M: u64] ModInt<M>, |_, _| M);
+impl_mod_ops!([] DynModInt, DynModInt::check_modulus);
+
+macro_rules! impl_mod_from {
+    ($($t:ty),*) => {$(
+        impl<const M: u64> From<$t> for ModInt<M> {
+            // Negative values wrap around, so -1 becomes M - 1
+            fn from(value: $t) -> Self {
+                Self::new((value as i128).rem_euclid(M as i128) as u64)
+            }
+        }
+    )*};
+}
+
+impl_mod_from!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, usize);
+
+impl<const M: u64> From<u128> for ModInt<M> {
+    fn from(value: u128) -> Self {
+        Self::new((value % M as u128) as u64)
+    }
+}
+
+impl<const M: u64> Zero for ModInt<M> {
+    fn zero() -> Self {
+        Self::new(0)
+    }
+
+    fn is_zero(&self) -> bool {
+        self.value == 0
+    }
+}
+
+impl<const M: u64> One for ModInt<M> {
+    fn one() -> Self {
+        Self::new(1)
+    }
+}
+
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<'a, const M: u64> Sum<&'a ModInt<M>> for ModInt<M> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), Mul::mul)
+    }
+}
+
+impl<'a, const M: u64> Product<&'a ModInt<M>> for ModInt<M> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vec2;
+
+    type M7 = ModInt<7>;
+
+    #[test]
+    fn test_mod_int() {
+        let a = M7::from(10);
+        let b = M7::from(-1);
+        assert_eq!((a.value(), b.value()), (3, 6));
+        assert_eq!((a + b).value(), 2);
+        assert_eq!((a - b).value(), 4);
+        assert_eq!((a * b).value(), 4);
+        assert_eq!((a / b).value(), 4);
+        assert_eq!((-a).value(), 4);
+        assert_eq!(a.pow(6), M7::one());
+        assert_eq!(a.inv().unwrap() * a, M7::one());
+        assert_eq!(ModInt::<8>::new(4).inv(), None);
+
+        let big = ModInt1e9_7::from(u64::MAX);
+        assert_eq!((big * big).value(), 114944269);
+        assert_eq!(ModInt1e9_7::new(2).pow(1_000_000_006).value(), 1);
+
+        let factorial: M7 = (1..=6).map(M7::from).product();
+        assert_eq!(factorial.value(), 6);
+        assert_eq!((0..100).map(M7::from).sum::<M7>().value(), 4950 % 7);
+
+        let v = Vec2::new(M7::from(5), M7::from(6)) * M7::from(2);
+        assert_eq!(v, Vec2::new(M7::from(3), M7::from(5)));
+        assert_eq!(format!("{}", v.x), "3");
+    }
+
+    #[test]
+    fn test_dyn_mod_int() {
+        let a = DynModInt::new(10, 7);
+        let b = DynModInt::from_signed(-1, 7);
+        assert_eq!((a + b).value(), 2);
+        assert_eq!((a / b).value(), 4);
+        assert_eq!(a.pow(6), DynModInt::one(7));
+        assert_eq!(u64::from(-a), 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_dyn_mod_int_mismatch() {
+        let _ = DynModInt::new(1, 7) + DynModInt::new(1, 11);
+    }
+}
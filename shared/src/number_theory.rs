@@ -0,0 +1,175 @@
+use crate::{Integer, Signed};
+
+// Always non-negative, and gcd(0, 0) is 0
+pub fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while !b.is_zero() {
+        (a, b) = (b, a % b);
+    }
+
+    if a < T::zero() {
+        T::zero() - a
+    } else {
+        a
+    }
+}
+
+// Always non-negative, and 0 if either is 0
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a.is_zero() || b.is_zero() {
+        return T::zero();
+    }
+
+    let result = a / gcd(a, b) * b;
+    if result < T::zero() {
+        T::zero() - result
+    } else {
+        result
+    }
+}
+
+// Returns (g, x, y) with a * x + b * y == g == gcd(a, b)
+pub fn extended_gcd<T: Integer + Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::one(), T::zero());
+    let (mut old_y, mut y) = (T::zero(), T::one());
+
+    while !r.is_zero() {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    if old_r < T::zero() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+// The x in 0..m with a * x == 1 (mod m), if a and m are coprime
+pub fn mod_inverse<T: Integer + Signed>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    if g == T::one() {
+        Some(x.rem_euclid(m))
+    } else {
+        None
+    }
+}
+
+// Solves x == r (mod m) for every (r, m) pair, returning (x, lcm of all m) with x in 0..lcm.
+// The moduli don't need to be coprime, in which case there is no solution if the congruences
+// contradict each other. Intermediate products need to fit in i128, so keep moduli in i64.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(r1, m1), (r2, m2)| {
+            let r2 = r2.rem_euclid(m2);
+            let g = gcd(m1, m2);
+            let diff = r2 - r1;
+            if diff % g != 0 {
+                return None;
+            }
+
+            // r1 + m1 * k == r2 (mod m2), solved for k modulo m2 / g
+            let m2_g = m2 / g;
+            let k = (diff / g).rem_euclid(m2_g) * mod_inverse(m1 / g, m2_g)? % m2_g;
+            let m = m1 * m2_g;
+            Some(((r1 + m1 * k).rem_euclid(m), m))
+        })
+}
+
+// The times `start`, `start + stride`, `start + 2 * stride`, ..., or only `start` if the
+// stride is 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Periodic {
+    pub start: u64,
+    pub stride: u64,
+}
+
+impl Periodic {
+    pub fn new(start: u64, stride: u64) -> Self {
+        Self { start, stride }
+    }
+
+    pub fn contains(&self, time: u64) -> bool {
+        time >= self.start
+            && match self.stride {
+                0 => time == self.start,
+                stride => (time - self.start).is_multiple_of(stride),
+            }
+    }
+
+    // The times shared by both, if any
+    pub fn intersect(&self, other: &Periodic) -> Option<Periodic> {
+        if self.stride == 0 {
+            return other.contains(self.start).then_some(*self);
+        }
+        if other.stride == 0 {
+            return self.contains(other.start).then_some(*other);
+        }
+
+        let (r, m) = crt([
+            (self.start as i128, self.stride as i128),
+            (other.start as i128, other.stride as i128),
+        ])?;
+
+        // Move up to the first matching time where both have started
+        let min = self.start.max(other.start) as i128;
+        let start = r + (min - r + m - 1).div_euclid(m) * m;
+        Some(Periodic::new(start as u64, m as u64))
+    }
+
+    // The first time contained in all of them
+    pub fn first_common<'a>(periods: impl IntoIterator<Item = &'a Periodic>) -> Option<u64> {
+        let mut periods = periods.into_iter();
+        let first = *periods.next()?;
+        periods
+            .try_fold(first, |acc, p| acc.intersect(p))
+            .map(|p| p.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12i64, 18), 6);
+        assert_eq!(gcd(0u8, 0), 0);
+        assert_eq!(lcm(4u64, 6), 12);
+        assert_eq!(lcm(-4i32, 6), 12);
+
+        let (g, x, y) = extended_gcd(240i64, 46);
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+
+        assert_eq!(mod_inverse(3i32, 11), Some(4));
+        assert_eq!(mod_inverse(-3i32, 11), Some(7));
+        assert_eq!(mod_inverse(6i32, 9), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+
+        let a = Periodic::new(2, 4);
+        let b = Periodic::new(8, 6);
+        assert_eq!(a.intersect(&b), Some(Periodic::new(14, 12)));
+        assert_eq!(a.intersect(&Periodic::new(3, 6)), None);
+        assert_eq!(
+            a.intersect(&Periodic::new(10, 0)),
+            Some(Periodic::new(10, 0))
+        );
+        assert_eq!(
+            Periodic::first_common(&[a, b, Periodic::new(0, 5)]),
+            Some(50)
+        );
+        assert_eq!(Periodic::first_common(&[]), None);
+    }
+}
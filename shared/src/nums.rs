@@ -1,183 +1,203 @@
-pub trait One {
-    fn one() -> Self;
-}
+use std::ops::*;
 
-impl One for i32 {
-    fn one() -> Self {
-        1
-    }
+pub trait Zero {
+    fn zero() -> Self;
+    fn is_zero(&self) -> bool;
 }
 
-impl One for f32 {
-    fn one() -> Self {
-        1.0
-    }
+pub trait One {
+    fn one() -> Self;
 }
 
-impl One for f64 {
-    fn one() -> Self {
-        1.0
-    }
+// Anything with the usual arithmetic, both integers and floats
+pub trait Num:
+    Copy
+    + PartialEq
+    + PartialOrd
+    + Zero
+    + One
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
 }
 
-impl One for u32 {
-    fn one() -> Self {
-        1
-    }
+pub trait Signed: Num + Neg<Output = Self> {
+    fn abs(self) -> Self;
+    // -1, 0 or 1, and NaN for NaN
+    fn signum(self) -> Self;
 }
 
-impl One for u64 {
-    fn one() -> Self {
-        1
-    }
-}
+pub trait Integer: Num + Ord + Eq + std::hash::Hash {
+    const MIN: Self;
+    const MAX: Self;
 
-impl One for usize {
-    fn one() -> Self {
-        1
-    }
-}
+    // Rounds towards negative infinity, unlike `/` which rounds towards zero
+    fn div_floor(self, rhs: Self) -> Self;
+    // Never negative, so that `div_euclid(a, b) * b + rem_euclid(a, b) == a`
+    fn div_euclid(self, rhs: Self) -> Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
 
-impl One for i64 {
-    fn one() -> Self {
-        1
-    }
-}
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+    fn checked_div(self, rhs: Self) -> Option<Self>;
+    fn checked_rem(self, rhs: Self) -> Option<Self>;
 
-impl One for i128 {
-    fn one() -> Self {
-        1
+    fn is_even(self) -> bool {
+        (self % (Self::one() + Self::one())).is_zero()
     }
-}
 
-impl One for u128 {
-    fn one() -> Self {
-        1
-    }
-}
-
-impl One for isize {
-    fn one() -> Self {
-        1
+    fn is_odd(self) -> bool {
+        !self.is_even()
     }
 }
 
 pub trait ToUnsignedIndex {
+    // None if negative or too large for usize
     fn to_index(self) -> Option<usize>;
 }
 
-impl ToUnsignedIndex for i32 {
-    fn to_index(self) -> Option<usize> {
-        if self < 0 {
-            None
-        } else {
-            Some(self as usize)
-        }
-    }
-}
-
-impl ToUnsignedIndex for u32 {
-    fn to_index(self) -> Option<usize> {
-        Some(self as usize)
-    }
+pub trait ToSignedIndex {
+    // None if too large for isize
+    fn to_index(self) -> Option<isize>;
 }
 
-impl ToUnsignedIndex for u64 {
-    fn to_index(self) -> Option<usize> {
-        Some(self as usize)
-    }
-}
+macro_rules! impl_num {
+    ($zero:expr, $one:expr, $($t:ty),*) => {$(
+        impl Zero for $t {
+            fn zero() -> Self {
+                $zero
+            }
 
-impl ToUnsignedIndex for usize {
-    fn to_index(self) -> Option<usize> {
-        Some(self)
-    }
-}
-
-impl ToUnsignedIndex for i64 {
-    fn to_index(self) -> Option<usize> {
-        if self < 0 {
-            None
-        } else {
-            Some(self as usize)
+            fn is_zero(&self) -> bool {
+                *self == $zero
+            }
         }
-    }
-}
 
-impl ToUnsignedIndex for i128 {
-    fn to_index(self) -> Option<usize> {
-        if self < 0 {
-            None
-        } else {
-            Some(self as usize)
+        impl One for $t {
+            fn one() -> Self {
+                $one
+            }
         }
-    }
-}
-
-impl ToUnsignedIndex for u128 {
-    fn to_index(self) -> Option<usize> {
-        Some(self as usize)
-    }
-}
 
-impl ToUnsignedIndex for isize {
-    fn to_index(self) -> Option<usize> {
-        if self < 0 {
-            None
-        } else {
-            Some(self as usize)
+        impl Num for $t {}
+    )*};
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {$(
+        impl Integer for $t {
+            const MIN: Self = <$t>::MIN;
+            const MAX: Self = <$t>::MAX;
+
+            // Rounding only differs for signed types, where the remainder takes the sign of self
+            #[allow(unused_comparisons)]
+            fn div_floor(self, rhs: Self) -> Self {
+                let (q, r) = (self / rhs, self % rhs);
+                if r != 0 && ((r < 0) != (rhs < 0)) {
+                    q - 1
+                } else {
+                    q
+                }
+            }
+
+            fn div_euclid(self, rhs: Self) -> Self {
+                <$t>::div_euclid(self, rhs)
+            }
+
+            fn rem_euclid(self, rhs: Self) -> Self {
+                <$t>::rem_euclid(self, rhs)
+            }
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_mul(self, rhs)
+            }
+
+            fn checked_div(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_div(self, rhs)
+            }
+
+            fn checked_rem(self, rhs: Self) -> Option<Self> {
+                <$t>::checked_rem(self, rhs)
+            }
         }
-    }
-}
 
-pub trait ToSignedIndex {
-    fn to_index(self) -> isize;
-}
-
-impl ToSignedIndex for i32 {
-    fn to_index(self) -> isize {
-        self as isize
-    }
-}
-
-impl ToSignedIndex for u32 {
-    fn to_index(self) -> isize {
-        self as isize
-    }
-}
-
-impl ToSignedIndex for u64 {
-    fn to_index(self) -> isize {
-        self as isize
-    }
-}
-
-impl ToSignedIndex for usize {
-    fn to_index(self) -> isize {
-        self as isize
-    }
-}
-
-impl ToSignedIndex for i64 {
-    fn to_index(self) -> isize {
-        self as isize
-    }
-}
+        impl ToUnsignedIndex for $t {
+            fn to_index(self) -> Option<usize> {
+                usize::try_from(self).ok()
+            }
+        }
 
-impl ToSignedIndex for i128 {
-    fn to_index(self) -> isize {
-        self as isize
-    }
+        impl ToSignedIndex for $t {
+            fn to_index(self) -> Option<isize> {
+                isize::try_from(self).ok()
+            }
+        }
+    )*};
 }
 
-impl ToSignedIndex for u128 {
-    fn to_index(self) -> isize {
-        self as isize
-    }
-}
+macro_rules! impl_signed {
+    ($($t:ty),*) => {$(
+        impl Signed for $t {
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
 
-impl ToSignedIndex for isize {
-    fn to_index(self) -> isize {
-        self
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        }
+    )*};
+}
+
+impl_num!(0, 1, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_num!(0.0, 1.0, f32, f64);
+impl_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize, f32, f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn floor_and_euclid<T: Integer>(a: T, b: T) -> (T, T, T) {
+        (
+            a.div_floor(b),
+            Integer::div_euclid(a, b),
+            Integer::rem_euclid(a, b),
+        )
+    }
+
+    #[test]
+    fn test_integer_ops() {
+        assert_eq!(floor_and_euclid(7i8, 2), (3, 3, 1));
+        assert_eq!(floor_and_euclid(-7i16, 2), (-4, -4, 1));
+        assert_eq!(floor_and_euclid(7i32, -2), (-4, -3, 1));
+        assert_eq!(floor_and_euclid(-7i64, -2), (3, 4, 1));
+        assert_eq!(floor_and_euclid(7u8, 2), (3, 3, 1));
+        assert_eq!(Integer::checked_add(250u8, 10), None);
+        assert_eq!(Integer::checked_mul(-3i16, 4), Some(-12));
+        assert!(Integer::is_even(-4i32) && Integer::is_odd(7u64));
+        assert_eq!(Signed::signum(-5i8), -1);
+        assert_eq!(<u16 as Zero>::zero(), 0);
+    }
+
+    #[test]
+    fn test_index_conversions() {
+        assert_eq!(ToUnsignedIndex::to_index(200u8), Some(200));
+        assert_eq!(ToUnsignedIndex::to_index(-1i16), None);
+        assert_eq!(ToSignedIndex::to_index(-1i16), Some(-1));
+        assert_eq!(ToSignedIndex::to_index(u64::MAX), None);
+        assert_eq!(ToSignedIndex::to_index(i128::MIN), None);
     }
 }
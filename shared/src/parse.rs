@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use crate::Grid2;
+use crate::{Grid2, Grid2Error};
 
 pub fn parse_lines_words(input: &str) -> Vec<Vec<&str>> {
     input
@@ -47,20 +47,31 @@ where
         .collect::<Vec<_>>()
 }
 
-pub fn parse_grid2<T: FromStr>(input: &str) -> Grid2<T>
-where
-    T::Err: std::fmt::Debug,
-{
+// Parses one cell per character. Empty input, ragged rows and unparseable characters are
+// reported rather than panicking.
+pub fn try_parse_grid2<T: FromStr>(input: &str) -> Result<Grid2<T>, Grid2Error> {
+    // Trailing blank lines would otherwise show up as empty rows
     let parsed = input
+        .trim_end_matches(['\n', '\r'])
         .lines()
-        .map(|l| {
+        .enumerate()
+        .map(|(row, l)| {
             l.chars()
-                .map(|c| c.to_string().parse::<T>().unwrap())
-                .collect::<Vec<_>>()
+                .enumerate()
+                .map(|(col, c)| {
+                    c.to_string()
+                        .parse::<T>()
+                        .map_err(|_| Grid2Error::InvalidCell { row, col, value: c })
+                })
+                .collect::<Result<Vec<_>, _>>()
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Grid2::from_rows(parsed)
+}
 
-    parsed.into()
+pub fn parse_grid2<T: FromStr>(input: &str) -> Grid2<T> {
+    try_parse_grid2(input).unwrap_or_else(|err| panic!("{}", err))
 }
 
 pub trait SplitHelpers {
@@ -106,3 +117,34 @@ impl<T: AsRef<str>> SplitHelpers for T {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Pos2;
+
+    #[test]
+    fn test_try_parse_grid2() {
+        let grid = try_parse_grid2::<u8>("12\n34\n\n").unwrap();
+        assert_eq!((grid.width(), grid.height()), (2, 2));
+        assert_eq!(grid[Pos2::new(1, 1)], 4);
+
+        assert_eq!(try_parse_grid2::<u8>("\n"), Err(Grid2Error::NoRows));
+        assert_eq!(
+            try_parse_grid2::<u8>("12\n3"),
+            Err(Grid2Error::RaggedRow {
+                row: 1,
+                width: 1,
+                expected_width: 2
+            })
+        );
+        assert_eq!(
+            try_parse_grid2::<u8>("12\n3x"),
+            Err(Grid2Error::InvalidCell {
+                row: 1,
+                col: 1,
+                value: 'x'
+            })
+        );
+    }
+}
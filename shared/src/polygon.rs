@@ -0,0 +1,155 @@
+use crate::{gcd, Line2, Pos2, Vec2};
+
+// A simple lattice polygon. The last vertex connects back to the first, so the start doesn't
+// need to be repeated at the end. All measurements are exact and run in O(vertices), no
+// matter how long the edges are.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Polygon {
+    vertices: Vec<Pos2<i64>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointLocation {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Pos2<i64>>) -> Self {
+        Self { vertices }
+    }
+
+    // Traces the polygon by walking `length` steps along each direction, e.g. a dig plan of
+    // (Dir4::Right.to_vec(), 6), (Dir4::Down.to_vec(), 5), ...
+    pub fn from_moves(start: Pos2<i64>, moves: impl IntoIterator<Item = (Vec2<i64>, i64)>) -> Self {
+        let mut vertices = vec![start];
+        let mut pos = start;
+        for (dir, length) in moves {
+            pos += dir * length;
+            vertices.push(pos);
+        }
+
+        // A closed walk ends where it started
+        if vertices.len() > 1 && vertices.last() == vertices.first() {
+            vertices.pop();
+        }
+
+        Self { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Pos2<i64>] {
+        &self.vertices
+    }
+
+    pub fn iter_edges(&self) -> impl '_ + Iterator<Item = (Pos2<i64>, Pos2<i64>)> {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    // Shoelace formula. Positive when the vertices go counter-clockwise with y pointing up,
+    // which is clockwise on screen in grid space.
+    pub fn signed_double_area(&self) -> i128 {
+        self.iter_edges()
+            .map(|(a, b)| a.x as i128 * b.y as i128 - b.x as i128 * a.y as i128)
+            .sum()
+    }
+
+    // Twice the area, which is always an integer for lattice polygons
+    pub fn double_area(&self) -> i128 {
+        self.signed_double_area().abs()
+    }
+
+    // Rounds down when the area is a half integer, which needs diagonal edges
+    pub fn area(&self) -> i128 {
+        self.double_area() / 2
+    }
+
+    // Number of lattice points on the edges
+    pub fn boundary_points(&self) -> i128 {
+        self.iter_edges()
+            .map(|(a, b)| gcd((b.x - a.x) as i128, (b.y - a.y) as i128))
+            .sum()
+    }
+
+    // Number of lattice points strictly inside, from Pick's theorem A = I + B / 2 - 1
+    pub fn interior_points(&self) -> i128 {
+        (self.double_area() - self.boundary_points() + 2) / 2
+    }
+
+    // Number of lattice points inside or on the boundary, e.g. the tiles covered by a loop
+    // that is one tile wide
+    pub fn enclosed_points(&self) -> i128 {
+        self.interior_points() + self.boundary_points()
+    }
+
+    pub fn locate(&self, pos: Pos2<i64>) -> PointLocation {
+        let mut inside = false;
+        for (a, b) in self.iter_edges() {
+            if Line2::segment(a, b).contains(pos) {
+                return PointLocation::Boundary;
+            }
+
+            // Count edges crossing the ray from `pos` towards +x
+            if (a.y > pos.y) != (b.y > pos.y) {
+                let (a, b, p) = (a.map(i128::from), b.map(i128::from), pos.map(i128::from));
+                let dy = b.y - a.y;
+                let side = (b.x - a.x) * (p.y - a.y) - (p.x - a.x) * dy;
+                if (side > 0) == (dy > 0) {
+                    inside = !inside;
+                }
+            }
+        }
+
+        if inside {
+            PointLocation::Inside
+        } else {
+            PointLocation::Outside
+        }
+    }
+
+    // Includes the boundary
+    pub fn contains(&self, pos: Pos2<i64>) -> bool {
+        self.locate(pos) != PointLocation::Outside
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polygon() {
+        // A 7x7 tile square with a 2x2 notch cut into the right side
+        let moves = [
+            ((1, 0), 6),
+            ((0, 1), 2),
+            ((-1, 0), 2),
+            ((0, 1), 2),
+            ((1, 0), 2),
+            ((0, 1), 2),
+            ((-1, 0), 6),
+            ((0, -1), 6),
+        ]
+        .map(|((x, y), n)| (Vec2::new(x, y), n));
+        let polygon = Polygon::from_moves(Pos2::new(0, 0), moves);
+
+        assert_eq!(polygon.vertices().len(), 8);
+        assert_eq!(polygon.area(), 36 - 4);
+        assert_eq!(polygon.boundary_points(), 28);
+        assert_eq!(polygon.interior_points(), 19);
+        assert_eq!(polygon.enclosed_points(), 49 - 2);
+
+        assert_eq!(polygon.locate(Pos2::new(1, 1)), PointLocation::Inside);
+        assert_eq!(polygon.locate(Pos2::new(5, 3)), PointLocation::Outside);
+        assert_eq!(polygon.locate(Pos2::new(4, 3)), PointLocation::Boundary);
+        assert_eq!(polygon.locate(Pos2::new(0, 6)), PointLocation::Boundary);
+        assert_eq!(polygon.locate(Pos2::new(-1, 2)), PointLocation::Outside);
+        assert!(polygon.contains(Pos2::new(3, 5)));
+
+        let triangle = Polygon::new(vec![Pos2::new(0, 0), Pos2::new(4, 0), Pos2::new(0, 3)]);
+        assert_eq!(triangle.double_area(), 12);
+        assert_eq!(triangle.boundary_points(), 4 + 1 + 3);
+        assert_eq!(triangle.interior_points(), 3);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::Rational;
+
+pub fn differences(values: &[i128]) -> Vec<i128> {
+    values.windows(2).map(|w| w[1] - w[0]).collect()
+}
+
+// The original values followed by each row of differences, down to the first row that is all
+// zeros. Without such a row the values aren't (yet) known to be polynomial.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DifferenceTable {
+    rows: Vec<Vec<i128>>,
+}
+
+impl DifferenceTable {
+    pub fn new(values: &[i128]) -> Self {
+        let mut rows = vec![values.to_vec()];
+        loop {
+            let last = rows.last().unwrap();
+            if last.is_empty() || last.iter().all(|&v| v == 0) {
+                break;
+            }
+            rows.push(differences(last));
+        }
+
+        Self { rows }
+    }
+
+    pub fn rows(&self) -> &[Vec<i128>] {
+        &self.rows
+    }
+
+    // Whether the table ended on a row of zeros rather than running out of values
+    pub fn is_polynomial(&self) -> bool {
+        self.rows.last().is_some_and(|row| !row.is_empty())
+    }
+
+    // Degree of the polynomial through the values, where all zeros have no degree
+    pub fn degree(&self) -> Option<usize> {
+        if self.is_polynomial() {
+            self.rows.len().checked_sub(2)
+        } else {
+            None
+        }
+    }
+
+    // Number of zeros in the final row, i.e. how many values more than strictly needed
+    // confirmed the degree
+    pub fn confirmations(&self) -> usize {
+        if self.is_polynomial() {
+            self.rows.last().unwrap().len()
+        } else {
+            0
+        }
+    }
+
+    // The value after the last one, found by extending every row by one
+    pub fn extrapolate_next(&self) -> i128 {
+        self.rows.iter().filter_map(|row| row.last()).sum()
+    }
+
+    // The value before the first one
+    pub fn extrapolate_prev(&self) -> i128 {
+        self.rows
+            .iter()
+            .rev()
+            .filter_map(|row| row.first())
+            .fold(0, |prev, first| first - prev)
+    }
+
+    // The polynomial through the values, with index 0 being the first value
+    pub fn to_polynomial(&self) -> Option<SequencePolynomial> {
+        self.is_polynomial().then(|| SequencePolynomial {
+            start: 0,
+            newton: self.rows[..self.rows.len() - 1]
+                .iter()
+                .map(|row| row[0])
+                .collect(),
+        })
+    }
+}
+
+// A polynomial sequence in Newton's forward difference form, so that
+// f(start + n) = sum of binomial(n, k) * newton[k]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SequencePolynomial {
+    pub start: i64,
+    pub newton: Vec<i128>,
+}
+
+// Generalized binomial coefficients binomial(n, 0), binomial(n, 1), ..., which are integers
+// even for negative n
+fn binomials(n: i128, count: usize) -> impl Iterator<Item = i128> {
+    (0..count as i128).scan(1, move |c, k| {
+        let result = *c;
+        // Exact, as c * (n - k) == (k + 1) * binomial(n, k + 1)
+        *c = *c * (n - k) / (k + 1);
+        Some(result)
+    })
+}
+
+impl SequencePolynomial {
+    // Fits the values at start, start + 1, ..., if they are polynomial
+    pub fn fit(start: i64, values: &[i128]) -> Option<Self> {
+        let mut poly = DifferenceTable::new(values).to_polynomial()?;
+        poly.start = start;
+        Some(poly)
+    }
+
+    pub fn degree(&self) -> Option<usize> {
+        self.newton.len().checked_sub(1)
+    }
+
+    // Exact for any index, including ones before the start
+    pub fn eval(&self, index: i64) -> i128 {
+        let n = index as i128 - self.start as i128;
+        binomials(n, self.newton.len())
+            .zip(&self.newton)
+            .map(|(b, d)| b * d)
+            .sum()
+    }
+}
+
+// Finds the smallest offset after which the values are polynomial, backed by at least
+// `confirmations` extra values beyond what the degree needs. The polynomial keeps indexing
+// from the start of `values`.
+pub fn find_polynomial_tail(
+    values: &[i128],
+    confirmations: usize,
+) -> Option<(usize, SequencePolynomial)> {
+    (0..values.len()).find_map(|offset| {
+        let table = DifferenceTable::new(&values[offset..]);
+        if table.confirmations() < confirmations.max(1) {
+            return None;
+        }
+        let mut poly = table.to_polynomial()?;
+        poly.start = offset as i64;
+        Some((offset, poly))
+    })
+}
+
+// Evaluates the polynomial through the given (x, y) points at x. The x values must be distinct.
+pub fn lagrange(points: &[(i128, i128)], x: i128) -> Rational {
+    points
+        .iter()
+        .enumerate()
+        .map(|(i, &(xi, yi))| {
+            points
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| i != j)
+                .map(|(_, &(xj, _))| Rational::new(x - xj, xi - xj))
+                .product::<Rational>()
+                * Rational::from(yi)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_difference_table() {
+        let table = DifferenceTable::new(&[10, 13, 16, 21, 30, 45]);
+        assert_eq!(table.degree(), Some(3));
+        assert_eq!(table.confirmations(), 2);
+        assert_eq!(table.extrapolate_next(), 68);
+        assert_eq!(table.extrapolate_prev(), 5);
+
+        assert_eq!(DifferenceTable::new(&[0, 0]).degree(), None);
+        assert!(DifferenceTable::new(&[0, 0]).is_polynomial());
+        assert!(!DifferenceTable::new(&[1, 2, 4]).is_polynomial());
+
+        let poly = table.to_polynomial().unwrap();
+        assert_eq!(poly.eval(6), 68);
+        assert_eq!(poly.eval(-1), 5);
+    }
+
+    #[test]
+    fn test_polynomial_fit() {
+        let quadratic = (0..6).map(|n| 3 * n * n - 2 * n + 7).collect::<Vec<_>>();
+        let poly = SequencePolynomial::fit(100, &quadratic).unwrap();
+        assert_eq!(poly.degree(), Some(2));
+        for n in -50..50 {
+            assert_eq!(
+                poly.eval(100 + n),
+                3 * n as i128 * n as i128 - 2 * n as i128 + 7
+            );
+        }
+
+        let mut values = vec![9, -4, 100];
+        values.extend((0..5).map(|n| n * n));
+        let (offset, poly) = find_polynomial_tail(&values, 2).unwrap();
+        assert_eq!(offset, 3);
+        assert_eq!(poly.eval(10), 49);
+
+        assert_eq!(lagrange(&[(0, 0), (1, 1), (2, 4)], 5), Rational::from(25));
+        assert_eq!(lagrange(&[(0, 0), (2, 1)], 1), Rational::new(1, 2));
+        assert_eq!(lagrange(&[(1, 3), (-1, 3), (3, 11)], -2), Rational::from(6));
+    }
+}
@@ -0,0 +1,232 @@
+use crate::{
+    gcd,
+    modint::{mul_mod, pow_mod},
+};
+
+// All primes up to and including `limit`
+pub fn sieve(limit: u64) -> Vec<u64> {
+    primes_in_range(0, limit + 1)
+}
+
+// All primes in lo..hi. Only sieves one block of the range at a time, using the primes up to
+// sqrt(hi), so large bounds don't need memory proportional to hi.
+pub fn primes_in_range(lo: u64, hi: u64) -> Vec<u64> {
+    const BLOCK: u64 = 1 << 16;
+
+    let lo = lo.max(2);
+    if lo >= hi {
+        return Vec::new();
+    }
+
+    let base = small_sieve((hi - 1).isqrt());
+    let mut result = Vec::new();
+    let mut start = lo;
+    while start < hi {
+        let end = hi.min(start.saturating_add(BLOCK));
+        let mut is_prime = vec![true; (end - start) as usize];
+
+        for &p in &base {
+            if p * p >= end {
+                break;
+            }
+            // First multiple of p in the block that isn't p itself
+            let first = (p * p).max(start.div_ceil(p) * p);
+            for multiple in (first..end).step_by(p as usize) {
+                is_prime[(multiple - start) as usize] = false;
+            }
+        }
+
+        result.extend(
+            is_prime
+                .iter()
+                .enumerate()
+                .filter(|(_, &prime)| prime)
+                .map(|(i, _)| start + i as u64),
+        );
+        start = end;
+    }
+
+    result
+}
+
+fn small_sieve(limit: u64) -> Vec<u64> {
+    let limit = limit as usize;
+    let mut is_prime = vec![true; limit + 1];
+    let mut primes = Vec::new();
+    for n in 2..=limit {
+        if is_prime[n] {
+            primes.push(n as u64);
+            for multiple in (n * n..=limit).step_by(n) {
+                is_prime[multiple] = false;
+            }
+        }
+    }
+    primes
+}
+
+// Deterministic Miller-Rabin, these bases are enough for every u64
+pub fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+    if n < 2 {
+        return false;
+    }
+    for p in BASES {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    BASES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        (1..s).any(|_| {
+            x = mul_mod(x, x, n);
+            x == n - 1
+        })
+    })
+}
+
+// Finds a non-trivial factor of an odd composite n
+fn pollard_rho(n: u64) -> u64 {
+    // Deterministic increments of the constant, in place of random restarts
+    for c in 1u128.. {
+        let f = |x: u64| ((mul_mod(x, x, n) as u128 + c) % n as u128) as u64;
+        let (mut x, mut y, mut d) = (2, 2, 1);
+        while d == 1 {
+            x = f(x);
+            y = f(f(y));
+            d = gcd(x.abs_diff(y), n);
+        }
+        if d != n {
+            return d;
+        }
+    }
+    unreachable!()
+}
+
+fn collect_prime_factors(n: u64, factors: &mut Vec<u64>) {
+    if n == 1 {
+        return;
+    }
+    if is_prime(n) {
+        factors.push(n);
+        return;
+    }
+
+    let d = pollard_rho(n);
+    collect_prime_factors(d, factors);
+    collect_prime_factors(n / d, factors);
+}
+
+// Prime factors with their exponents, in increasing order. Small factors are found by trial
+// division and anything left over by Pollard's rho.
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    assert!(n > 0, "Can't factorize 0");
+
+    let mut factors = Vec::new();
+    for p in [2, 3, 5].into_iter().chain((7..1000).step_by(2)) {
+        if p * p > n {
+            break;
+        }
+        while n.is_multiple_of(p) {
+            factors.push(p);
+            n /= p;
+        }
+    }
+
+    collect_prime_factors(n, &mut factors);
+    factors.sort_unstable();
+
+    let mut result = Vec::<(u64, u32)>::new();
+    for p in factors {
+        match result.last_mut() {
+            Some((last, count)) if *last == p => *count += 1,
+            _ => result.push((p, 1)),
+        }
+    }
+    result
+}
+
+// All divisors in increasing order, including 1 and n
+pub fn divisors(n: u64) -> Vec<u64> {
+    let mut result = vec![1];
+    for (p, exp) in factorize(n) {
+        let current = result.clone();
+        let mut power = 1;
+        for _ in 0..exp {
+            power *= p;
+            result.extend(current.iter().map(|d| d * power));
+        }
+    }
+    result.sort_unstable();
+    result
+}
+
+// Number of k in 1..=n that are coprime to n
+pub fn totient(n: u64) -> u64 {
+    factorize(n)
+        .into_iter()
+        .fold(n, |result, (p, _)| result / p * (p - 1))
+}
+
+// Sum of all divisors, including 1 and n
+pub fn sum_of_divisors(n: u64) -> u128 {
+    factorize(n)
+        .into_iter()
+        .map(|(p, exp)| {
+            let p = p as u128;
+            (0..=exp).map(|e| p.pow(e)).sum::<u128>()
+        })
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sieve() {
+        assert_eq!(sieve(30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!(sieve(1), vec![]);
+
+        let range = primes_in_range(1_000_000_000, 1_000_200_000);
+        assert!(range.iter().all(|&p| is_prime(p)));
+        let expected = (1_000_000_000..1_000_200_000u64)
+            .filter(|&n| is_prime(n))
+            .count();
+        assert_eq!(range.len(), expected);
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let primes = sieve(10_000);
+        for n in 0..10_000 {
+            assert_eq!(is_prime(n), primes.binary_search(&n).is_ok(), "{n}");
+        }
+        assert!(is_prime(18_446_744_073_709_551_557));
+        // Strong pseudoprime to several small bases
+        assert!(!is_prime(3_215_031_751));
+        assert!(!is_prime(4_294_967_297));
+    }
+
+    #[test]
+    fn test_factorize() {
+        assert_eq!(factorize(1), vec![]);
+        assert_eq!(factorize(360), vec![(2, 3), (3, 2), (5, 1)]);
+        assert_eq!(factorize(4_294_967_297), vec![(641, 1), (6_700_417, 1)]);
+        let big = 1_000_000_007u64 * 998_244_353;
+        assert_eq!(factorize(big), vec![(998_244_353, 1), (1_000_000_007, 1)]);
+
+        assert_eq!(divisors(12), vec![1, 2, 3, 4, 6, 12]);
+        assert_eq!(divisors(1), vec![1]);
+        assert_eq!(totient(36), 12);
+        assert_eq!(totient(1), 1);
+        assert_eq!(sum_of_divisors(12), 28);
+        assert_eq!(sum_of_divisors(big), 1_000_000_008 * 998_244_354);
+    }
+}
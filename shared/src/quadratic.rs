@@ -0,0 +1,226 @@
+use crate::Integer;
+
+// Exact floor of the square root
+pub fn isqrt(n: i128) -> i128 {
+    assert!(n >= 0, "Square root of negative number {n}");
+    (n as u128).isqrt() as i128
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Inequality {
+    Greater,
+    GreaterOrEqual,
+    Less,
+    LessOrEqual,
+}
+
+// A set of integers, as the solution to an inequality
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntegerSolutions {
+    Empty,
+    // Both ends inclusive, where None is unbounded
+    Range(Option<i128>, Option<i128>),
+    // Everything up to and including the first, and from the second onwards
+    Outside(i128, i128),
+}
+
+impl IntegerSolutions {
+    pub fn contains(&self, x: i128) -> bool {
+        match *self {
+            IntegerSolutions::Empty => false,
+            IntegerSolutions::Range(min, max) => {
+                min.is_none_or(|min| x >= min) && max.is_none_or(|max| x <= max)
+            }
+            IntegerSolutions::Outside(below, above) => x <= below || x >= above,
+        }
+    }
+
+    // None if there are infinitely many
+    pub fn count(&self) -> Option<u128> {
+        match *self {
+            IntegerSolutions::Empty => Some(0),
+            IntegerSolutions::Range(Some(min), Some(max)) => Some(max.abs_diff(min) + 1),
+            _ => None,
+        }
+    }
+}
+
+// a * x^2 + b * x + c
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Quadratic {
+    pub a: i128,
+    pub b: i128,
+    pub c: i128,
+}
+
+// First x in lo..=hi where `pred` holds, for a predicate that is false and then true.
+// Returns hi + 1 if it never holds.
+fn partition_point(mut lo: i128, mut hi: i128, pred: impl Fn(i128) -> bool) -> i128 {
+    hi += 1;
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    lo
+}
+
+impl Quadratic {
+    pub fn new(a: i128, b: i128, c: i128) -> Self {
+        Self { a, b, c }
+    }
+
+    // Panics rather than wrapping when the result doesn't fit in i128
+    pub fn eval(&self, x: i128) -> i128 {
+        let overflow = || panic!("Quadratic overflowed at x = {x}");
+        let ax2 = x
+            .checked_mul(x)
+            .and_then(|x2| x2.checked_mul(self.a))
+            .unwrap_or_else(overflow);
+        let bx = x.checked_mul(self.b).unwrap_or_else(overflow);
+        ax2.checked_add(bx)
+            .and_then(|v| v.checked_add(self.c))
+            .unwrap_or_else(overflow)
+    }
+
+    // Every integer x for which `eval(x) <op> 0` holds, found exactly without floats
+    pub fn solve(&self, op: Inequality) -> IntegerSolutions {
+        // Flip the sign so that only > 0 and >= 0 are left
+        let (q, strict) = match op {
+            Inequality::Greater => (*self, true),
+            Inequality::GreaterOrEqual => (*self, false),
+            Inequality::Less => (Quadratic::new(-self.a, -self.b, -self.c), true),
+            Inequality::LessOrEqual => (Quadratic::new(-self.a, -self.b, -self.c), false),
+        };
+        let holds = |x: i128| {
+            let v = q.eval(x);
+            v > 0 || (!strict && v == 0)
+        };
+
+        if q.a == 0 {
+            return q.solve_linear(strict);
+        }
+
+        // The integer extremum is at v or v + 1, as the real one lies in [v, v + 1)
+        let v = Integer::div_floor(-q.b, 2 * q.a);
+        let disc =
+            q.b.checked_mul(q.b)
+                .zip(q.a.checked_mul(q.c).and_then(|ac| ac.checked_mul(4)))
+                .and_then(|(b2, ac4)| b2.checked_sub(ac4))
+                .expect("Discriminant overflowed i128");
+        // No root is further than this from v
+        let reach = if disc > 0 {
+            isqrt(disc) / (2 * q.a.abs())
+        } else {
+            0
+        } + 3;
+
+        if q.a < 0 {
+            // Opening downwards, so the solutions are one range around the maximum
+            let Some(top) = [v, v + 1].into_iter().find(|&x| holds(x)) else {
+                return IntegerSolutions::Empty;
+            };
+            let min = partition_point(top - reach, top, holds);
+            let max = partition_point(top, top + reach, |x| !holds(x)) - 1;
+            IntegerSolutions::Range(Some(min), Some(max))
+        } else {
+            // Opening upwards, so the solutions are everything outside a range around the minimum
+            let Some(bottom) = [v, v + 1].into_iter().find(|&x| !holds(x)) else {
+                return IntegerSolutions::Range(None, None);
+            };
+            let below = partition_point(bottom - reach, bottom, |x| !holds(x)) - 1;
+            let above = partition_point(bottom, bottom + reach, holds);
+            IntegerSolutions::Outside(below, above)
+        }
+    }
+
+    fn solve_linear(&self, strict: bool) -> IntegerSolutions {
+        let (b, c) = (self.b, self.c);
+        match b.signum() {
+            0 if c > 0 || (!strict && c == 0) => IntegerSolutions::Range(None, None),
+            0 => IntegerSolutions::Empty,
+            // x > -c / b, or x >= -c / b
+            1 => {
+                let min = if strict {
+                    Integer::div_floor(-c, b) + 1
+                } else {
+                    -Integer::div_floor(c, b)
+                };
+                IntegerSolutions::Range(Some(min), None)
+            }
+            // x < c / -b, or x <= c / -b
+            _ => {
+                let max = if strict {
+                    -Integer::div_floor(-c, -b) - 1
+                } else {
+                    Integer::div_floor(c, -b)
+                };
+                IntegerSolutions::Range(None, Some(max))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brute_force(q: Quadratic, op: Inequality) -> Vec<i128> {
+        (-60..=60)
+            .filter(|&x| {
+                let v = q.eval(x);
+                match op {
+                    Inequality::Greater => v > 0,
+                    Inequality::GreaterOrEqual => v >= 0,
+                    Inequality::Less => v < 0,
+                    Inequality::LessOrEqual => v <= 0,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(24), 4);
+        assert_eq!(isqrt(25), 5);
+        let big = (1i128 << 62) + 12345;
+        assert_eq!(isqrt(big * big), big);
+        assert_eq!(isqrt(big * big - 1), big - 1);
+    }
+
+    #[test]
+    fn test_solve_matches_brute_force() {
+        let ops = [
+            Inequality::Greater,
+            Inequality::GreaterOrEqual,
+            Inequality::Less,
+            Inequality::LessOrEqual,
+        ];
+        for a in -3..=3 {
+            for b in -7..=7 {
+                for c in -9..=9 {
+                    let q = Quadratic::new(a, b, c);
+                    for op in ops {
+                        let solutions = q.solve(op);
+                        let expected = brute_force(q, op);
+                        let actual = (-60..=60)
+                            .filter(|&x| solutions.contains(x))
+                            .collect::<Vec<_>>();
+                        assert_eq!(actual, expected, "{q:?} {op:?} gave {solutions:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_race() {
+        // Holding the button for x out of 71530 ms must beat 940200 mm
+        let q = Quadratic::new(-1, 71530, -940200);
+        assert_eq!(q.solve(Inequality::Greater).count(), Some(71503));
+    }
+}
@@ -0,0 +1,292 @@
+use std::{cmp::Ordering, iter::*, ops::*, str::FromStr};
+
+use crate::{gcd, Integer, One, Signed, Zero};
+
+// An exact fraction, always stored in lowest terms with a positive denominator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational<T = i128> {
+    num: T,
+    den: T,
+}
+
+impl<T: Integer + Signed> Rational<T> {
+    pub fn new(num: T, den: T) -> Self {
+        assert!(!den.is_zero(), "Rational with zero denominator");
+
+        let g = gcd(num, den);
+        let (num, den) = (num / g, den / g);
+        if den < T::zero() {
+            Self {
+                num: -num,
+                den: -den,
+            }
+        } else {
+            Self { num, den }
+        }
+    }
+
+    pub fn from_integer(value: T) -> Self {
+        Self {
+            num: value,
+            den: T::one(),
+        }
+    }
+
+    pub fn num(self) -> T {
+        self.num
+    }
+
+    pub fn den(self) -> T {
+        self.den
+    }
+
+    pub fn is_integer(self) -> bool {
+        self.den == T::one()
+    }
+
+    pub fn to_integer(self) -> Option<T> {
+        self.is_integer().then_some(self.num)
+    }
+
+    pub fn floor(self) -> T {
+        self.num.div_floor(self.den)
+    }
+
+    pub fn ceil(self) -> T {
+        -(-self.num).div_floor(self.den)
+    }
+
+    pub fn abs(self) -> Self {
+        Self {
+            num: self.num.abs(),
+            den: self.den,
+        }
+    }
+
+    // Panics for zero
+    pub fn recip(self) -> Self {
+        Self::new(self.den, self.num)
+    }
+
+    pub fn pow(self, exp: u32) -> Self {
+        (0..exp).fold(Self::one(), |acc, _| acc * self)
+    }
+}
+
+impl Rational<i128> {
+    pub fn to_f64(self) -> f64 {
+        self.num as f64 / self.den as f64
+    }
+}
+
+impl<T: Integer + Signed> Zero for Rational<T> {
+    fn zero() -> Self {
+        Self::from_integer(T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.num.is_zero()
+    }
+}
+
+impl<T: Integer + Signed> One for Rational<T> {
+    fn one() -> Self {
+        Self::from_integer(T::one())
+    }
+}
+
+impl<T: Integer + Signed> Default for Rational<T> {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl<T: Integer + Signed> From<T> for Rational<T> {
+    fn from(value: T) -> Self {
+        Self::from_integer(value)
+    }
+}
+
+impl<T: Integer + Signed> PartialOrd for Rational<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Integer + Signed> Ord for Rational<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Denominators are positive, so cross multiplying keeps the order
+        (self.num * other.den).cmp(&(other.num * self.den))
+    }
+}
+
+impl<T: Integer + Signed + std::fmt::Display> std::fmt::Display for Rational<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.den == T::one() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRationalError(pub String);
+
+impl std::fmt::Display for ParseRationalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid rational: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseRationalError {}
+
+// Accepts "3", "-3/4" and "3 / 4"
+impl<T: Integer + Signed + FromStr> FromStr for Rational<T> {
+    type Err = ParseRationalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseRationalError(s.to_string());
+        let parse = |part: &str| part.trim().parse::<T>().map_err(|_| err());
+
+        match s.split_once('/') {
+            None => Ok(Self::from_integer(parse(s)?)),
+            Some((num, den)) => {
+                let den = parse(den)?;
+                if den.is_zero() {
+                    return Err(err());
+                }
+                Ok(Self::new(parse(num)?, den))
+            }
+        }
+    }
+}
+
+impl<T: Integer + Signed> Add for Rational<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        // Dividing by the gcd first keeps the intermediate values small
+        let g = gcd(self.den, rhs.den);
+        let num = self.num * (rhs.den / g) + rhs.num * (self.den / g);
+        Self::new(num, self.den / g * rhs.den)
+    }
+}
+
+impl<T: Integer + Signed> Sub for Rational<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<T: Integer + Signed> Mul for Rational<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let g1 = gcd(self.num, rhs.den).max(T::one());
+        let g2 = gcd(rhs.num, self.den).max(T::one());
+        Self::new(
+            (self.num / g1) * (rhs.num / g2),
+            (self.den / g2) * (rhs.den / g1),
+        )
+    }
+}
+
+impl<T: Integer + Signed> Div for Rational<T> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.recip()
+    }
+}
+
+impl<T: Integer + Signed> Rem for Rational<T> {
+    type Output = Self;
+
+    // Takes the sign of self, like integer `%`
+    fn rem(self, rhs: Self) -> Self::Output {
+        let q = self / rhs;
+        let truncated = if q < Self::zero() {
+            q.ceil()
+        } else {
+            q.floor()
+        };
+        self - rhs * Self::from_integer(truncated)
+    }
+}
+
+impl<T: Integer + Signed> Neg for Rational<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            num: -self.num,
+            den: self.den,
+        }
+    }
+}
+
+macro_rules! impl_rational_assign {
+    ($($trait:ident, $fn:ident, $op:tt;)*) => {$(
+        impl<T: Integer + Signed> $trait for Rational<T> {
+            fn $fn(&mut self, rhs: Self) {
+                *self = *self $op rhs;
+            }
+        }
+    )*};
+}
+
+impl_rational_assign!(
+    AddAssign, add_assign, +;
+    SubAssign, sub_assign, -;
+    MulAssign, mul_assign, *;
+    DivAssign, div_assign, /;
+    RemAssign, rem_assign, %;
+);
+
+impl<T: Integer + Signed> Sum for Rational<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), Add::add)
+    }
+}
+
+impl<T: Integer + Signed> Product for Rational<T> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), Mul::mul)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Q = Rational<i128>;
+
+    #[test]
+    fn test_rational() {
+        let a = Q::new(6, -8);
+        assert_eq!((a.num(), a.den()), (-3, 4));
+        assert_eq!(a + Q::new(1, 4), Q::new(-1, 2));
+        assert_eq!(a - Q::from(1), Q::new(-7, 4));
+        assert_eq!(a * Q::new(8, 3), Q::from(-2));
+        assert_eq!(a / Q::new(-3, 2), Q::new(1, 2));
+        assert_eq!(Q::new(7, 2) % Q::from(2), Q::new(3, 2));
+        assert_eq!((a.floor(), a.ceil()), (-1, 0));
+        assert_eq!(Q::new(1, 3).pow(3), Q::new(1, 27));
+        assert!(Q::new(1, 3) < Q::new(1, 2) && Q::new(-1, 2) < Q::new(-1, 3));
+        assert_eq!(Q::new(4, 2).to_integer(), Some(2));
+        assert_eq!(a.to_integer(), None);
+
+        assert_eq!("-3/4".parse::<Q>(), Ok(a));
+        assert_eq!(" 5 ".parse::<Q>(), Ok(Q::from(5)));
+        assert!("1/0".parse::<Q>().is_err());
+        assert_eq!(a.to_string(), "-3/4");
+        assert_eq!((1..=4).map(|n| Q::new(1, n)).sum::<Q>(), Q::new(25, 12));
+
+        let small = Rational::<i8>::new(100, 120);
+        assert_eq!((small * small).num(), 25);
+    }
+}
@@ -0,0 +1,419 @@
+use std::ops::*;
+
+use crate::{One, Pos2, Pos3, Vec2, Vec3};
+
+// An axis-aligned rectangle covering `min` up to but excluding `max`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rect2<T> {
+    min: Pos2<T>,
+    max: Pos2<T>,
+}
+
+// An axis-aligned box covering `min` up to but excluding `max`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Box3<T> {
+    min: Pos3<T>,
+    max: Pos3<T>,
+}
+
+impl<T: Copy + PartialOrd> Rect2<T> {
+    // The corners can be given in any order
+    pub fn from_exclusive(a: Pos2<T>, b: Pos2<T>) -> Self {
+        Self {
+            min: a.component_min(b),
+            max: a.component_max(b),
+        }
+    }
+
+    // The corners can be given in any order, and both are included in the rectangle
+    pub fn from_inclusive(a: Pos2<T>, b: Pos2<T>) -> Self
+    where
+        T: One + Add<Output = T>,
+    {
+        Self {
+            min: a.component_min(b),
+            max: a.component_max(b) + Vec2::new(T::one(), T::one()),
+        }
+    }
+
+    pub fn from_size(min: Pos2<T>, size: Vec2<T>) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        Self::from_exclusive(min, min + size)
+    }
+
+    pub fn min(&self) -> Pos2<T> {
+        self.min
+    }
+
+    // Exclusive
+    pub fn max(&self) -> Pos2<T> {
+        self.max
+    }
+
+    pub fn max_inclusive(&self) -> Pos2<T>
+    where
+        T: One + Sub<Output = T>,
+    {
+        self.max - Vec2::new(T::one(), T::one())
+    }
+
+    pub fn size(&self) -> Vec2<T>
+    where
+        T: Sub<Output = T>,
+    {
+        self.max - self.min
+    }
+
+    pub fn width(&self) -> T
+    where
+        T: Sub<Output = T>,
+    {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> T
+    where
+        T: Sub<Output = T>,
+    {
+        self.max.y - self.min.y
+    }
+
+    pub fn area(&self) -> T
+    where
+        T: Sub<Output = T> + Mul<Output = T>,
+    {
+        self.width() * self.height()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x >= self.max.x || self.min.y >= self.max.y
+    }
+
+    pub fn contains(&self, pos: Pos2<T>) -> bool {
+        pos.x >= self.min.x && pos.x < self.max.x && pos.y >= self.min.y && pos.y < self.max.y
+    }
+
+    pub fn contains_rect(&self, other: &Self) -> bool {
+        other.is_empty()
+            || (other.min.x >= self.min.x
+                && other.min.y >= self.min.y
+                && other.max.x <= self.max.x
+                && other.max.y <= self.max.y)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let rect = Self {
+            min: self.min.component_max(other.min),
+            max: self.max.component_min(other.max),
+        };
+
+        if rect.is_empty() {
+            None
+        } else {
+            Some(rect)
+        }
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    // The smallest rectangle containing both
+    pub fn union_bounds(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+
+        Self {
+            min: self.min.component_min(other.min),
+            max: self.max.component_max(other.max),
+        }
+    }
+
+    pub fn translate(&self, offset: Vec2<T>) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        Self {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    // Splits the part of `self` that isn't covered by `other` into disjoint rectangles
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(cut) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+
+        let pieces = [
+            // Full width strips above and below the cut
+            (self.min, Pos2::new(self.max.x, cut.min.y)),
+            (Pos2::new(self.min.x, cut.max.y), self.max),
+            // Strips left and right of the cut
+            (
+                Pos2::new(self.min.x, cut.min.y),
+                Pos2::new(cut.min.x, cut.max.y),
+            ),
+            (
+                Pos2::new(cut.max.x, cut.min.y),
+                Pos2::new(self.max.x, cut.max.y),
+            ),
+        ];
+
+        pieces
+            .into_iter()
+            .map(|(min, max)| Self { min, max })
+            .filter(|rect| !rect.is_empty())
+            .collect()
+    }
+
+    // Iterates the contained positions in reading order
+    pub fn iter(&self) -> impl Iterator<Item = Pos2<T>>
+    where
+        T: One + Add<Output = T>,
+    {
+        let (min, max) = (self.min, self.max);
+        let step = |c: &T| Some(*c + T::one());
+
+        std::iter::successors(Some(min.y), step)
+            .take_while(move |&y| y < max.y)
+            .flat_map(move |y| {
+                std::iter::successors(Some(min.x), step)
+                    .take_while(move |&x| x < max.x)
+                    .map(move |x| Pos2::new(x, y))
+            })
+    }
+}
+
+impl<T: Copy + PartialOrd> Box3<T> {
+    // The corners can be given in any order
+    pub fn from_exclusive(a: Pos3<T>, b: Pos3<T>) -> Self {
+        Self {
+            min: a.component_min(b),
+            max: a.component_max(b),
+        }
+    }
+
+    // The corners can be given in any order, and both are included in the box
+    pub fn from_inclusive(a: Pos3<T>, b: Pos3<T>) -> Self
+    where
+        T: One + Add<Output = T>,
+    {
+        Self {
+            min: a.component_min(b),
+            max: a.component_max(b) + Vec3::new(T::one(), T::one(), T::one()),
+        }
+    }
+
+    pub fn from_size(min: Pos3<T>, size: Vec3<T>) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        Self::from_exclusive(min, min + size)
+    }
+
+    pub fn min(&self) -> Pos3<T> {
+        self.min
+    }
+
+    // Exclusive
+    pub fn max(&self) -> Pos3<T> {
+        self.max
+    }
+
+    pub fn max_inclusive(&self) -> Pos3<T>
+    where
+        T: One + Sub<Output = T>,
+    {
+        self.max - Vec3::new(T::one(), T::one(), T::one())
+    }
+
+    pub fn size(&self) -> Vec3<T>
+    where
+        T: Sub<Output = T>,
+    {
+        self.max - self.min
+    }
+
+    pub fn volume(&self) -> T
+    where
+        T: Sub<Output = T> + Mul<Output = T>,
+    {
+        let size = self.size();
+        size.x * size.y * size.z
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x >= self.max.x || self.min.y >= self.max.y || self.min.z >= self.max.z
+    }
+
+    pub fn contains(&self, pos: Pos3<T>) -> bool {
+        pos.x >= self.min.x
+            && pos.x < self.max.x
+            && pos.y >= self.min.y
+            && pos.y < self.max.y
+            && pos.z >= self.min.z
+            && pos.z < self.max.z
+    }
+
+    pub fn contains_box(&self, other: &Self) -> bool {
+        other.is_empty()
+            || (other.min.x >= self.min.x
+                && other.min.y >= self.min.y
+                && other.min.z >= self.min.z
+                && other.max.x <= self.max.x
+                && other.max.y <= self.max.y
+                && other.max.z <= self.max.z)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let cut = Self {
+            min: self.min.component_max(other.min),
+            max: self.max.component_min(other.max),
+        };
+
+        if cut.is_empty() {
+            None
+        } else {
+            Some(cut)
+        }
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    // The smallest box containing both
+    pub fn union_bounds(&self, other: &Self) -> Self {
+        if self.is_empty() {
+            return *other;
+        }
+        if other.is_empty() {
+            return *self;
+        }
+
+        Self {
+            min: self.min.component_min(other.min),
+            max: self.max.component_max(other.max),
+        }
+    }
+
+    pub fn translate(&self, offset: Vec3<T>) -> Self
+    where
+        T: Add<Output = T>,
+    {
+        Self {
+            min: self.min + offset,
+            max: self.max + offset,
+        }
+    }
+
+    // Splits the part of `self` that isn't covered by `other` into disjoint boxes
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(cut) = self.intersection(other) else {
+            return if self.is_empty() { vec![] } else { vec![*self] };
+        };
+
+        let (min, max) = (self.min, self.max);
+        let pieces = [
+            // Full slabs below and above the cut along z
+            (min, Pos3::new(max.x, max.y, cut.min.z)),
+            (Pos3::new(min.x, min.y, cut.max.z), max),
+            // Within the cut's z range, full width strips before and after it along y
+            (
+                Pos3::new(min.x, min.y, cut.min.z),
+                Pos3::new(max.x, cut.min.y, cut.max.z),
+            ),
+            (
+                Pos3::new(min.x, cut.max.y, cut.min.z),
+                Pos3::new(max.x, max.y, cut.max.z),
+            ),
+            // Within the cut's y and z range, the pieces before and after it along x
+            (
+                Pos3::new(min.x, cut.min.y, cut.min.z),
+                Pos3::new(cut.min.x, cut.max.y, cut.max.z),
+            ),
+            (
+                Pos3::new(cut.max.x, cut.min.y, cut.min.z),
+                Pos3::new(max.x, cut.max.y, cut.max.z),
+            ),
+        ];
+
+        pieces
+            .into_iter()
+            .map(|(min, max)| Self { min, max })
+            .filter(|b| !b.is_empty())
+            .collect()
+    }
+
+    // Iterates the contained positions, x fastest, then y, then z
+    pub fn iter(&self) -> impl Iterator<Item = Pos3<T>>
+    where
+        T: One + Add<Output = T>,
+    {
+        let (min, max) = (self.min, self.max);
+        let step = |c: &T| Some(*c + T::one());
+        let range = move |from: T, to: T| {
+            std::iter::successors(Some(from), step).take_while(move |&c| c < to)
+        };
+
+        range(min.z, max.z).flat_map(move |z| {
+            range(min.y, max.y)
+                .flat_map(move |y| range(min.x, max.x).map(move |x| Pos3::new(x, y, z)))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rect2() {
+        let a = Rect2::from_inclusive(Pos2::new(3, 3), Pos2::new(0, 0));
+        assert_eq!(a.area(), 16);
+        assert!(a.contains(Pos2::new(3, 0)));
+        assert!(!a.contains(Pos2::new(4, 0)));
+        assert_eq!(a.iter().count(), 16);
+        assert_eq!(a.iter().nth(4), Some(Pos2::new(0, 1)));
+
+        let b = Rect2::from_exclusive(Pos2::new(2, 1), Pos2::new(6, 3));
+        let cut = a.intersection(&b).unwrap();
+        assert_eq!(cut, Rect2::from_inclusive(Pos2::new(2, 1), Pos2::new(3, 2)));
+        assert_eq!(a.union_bounds(&b).area(), 24);
+        assert!(a.intersection(&b.translate(Vec2::new(10, 0))).is_none());
+
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.iter().map(|p| p.area()).sum::<i32>(), 16 - 4);
+        for (i, p) in pieces.iter().enumerate() {
+            assert!(!p.intersects(&b));
+            assert!(pieces[i + 1..].iter().all(|q| !p.intersects(q)));
+        }
+    }
+
+    #[test]
+    fn test_box3() {
+        let a = Box3::from_inclusive(Pos3::new(0, 0, 0), Pos3::new(3, 3, 3));
+        let b = Box3::from_inclusive(Pos3::new(1, 1, 1), Pos3::new(1, 1, 5));
+        assert_eq!(a.volume(), 64);
+        assert_eq!(a.iter().count(), 64);
+        assert!(a.intersects(&b));
+        assert!(!a.contains_box(&b));
+
+        let pieces = a.subtract(&b);
+        assert_eq!(pieces.iter().map(|p| p.volume()).sum::<i32>(), 64 - 3);
+        for (i, p) in pieces.iter().enumerate() {
+            assert!(!p.intersects(&b));
+            assert!(a.contains_box(p));
+            assert!(pieces[i + 1..].iter().all(|q| !p.intersects(q)));
+        }
+
+        assert_eq!(a.subtract(&a), vec![]);
+    }
+}
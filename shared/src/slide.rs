@@ -0,0 +1,89 @@
+use crate::{Dir4, Grid2, Pos2};
+
+impl<T> Grid2<T> {
+    // Slides every movable cell as far as it can go in the given direction, stopping at
+    // blocking cells, the grid edge, or other movable cells that have already settled.
+    // Cells that are neither movable nor blocking are treated as empty space and get swapped
+    // into the vacated positions. Returns the number of cells that moved.
+    pub fn slide_cells(
+        &mut self,
+        dir: Dir4,
+        is_movable: impl Fn(&T) -> bool,
+        is_blocking: impl Fn(&T) -> bool,
+    ) -> usize {
+        let (lines, line_len) = match dir {
+            Dir4::Left | Dir4::Right => (self.height(), self.width()),
+            Dir4::Up | Dir4::Down => (self.width(), self.height()),
+        };
+
+        // Maps (line, step) to a position, where step 0 is the edge the cells slide towards
+        let to_pos = |line: usize, step: usize| match dir {
+            Dir4::Left => Pos2::new(step, line),
+            Dir4::Right => Pos2::new(line_len - 1 - step, line),
+            Dir4::Up => Pos2::new(line, step),
+            Dir4::Down => Pos2::new(line, line_len - 1 - step),
+        };
+
+        let mut moved = 0;
+        for line in 0..lines {
+            let mut next_free = 0;
+            for step in 0..line_len {
+                let pos = to_pos(line, step);
+                let cell = &self[pos];
+
+                if is_blocking(cell) {
+                    next_free = step + 1;
+                } else if is_movable(cell) {
+                    if next_free != step {
+                        self.swap(pos, to_pos(line, next_free));
+                        moved += 1;
+                    }
+                    next_free += 1;
+                }
+            }
+        }
+
+        moved
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Grid2<char> {
+        Grid2::from_rows(rows.iter().map(|row| row.chars())).unwrap()
+    }
+
+    fn slide(grid: &mut Grid2<char>, dir: Dir4) -> usize {
+        grid.slide_cells(dir, |&c| c == 'O', |&c| c == '#')
+    }
+
+    #[test]
+    fn test_slide_cells() {
+        let start = grid(&["O.#O.", ".....", "..O#.", "O...O"]);
+
+        let mut left = start.clone();
+        assert_eq!(slide(&mut left, Dir4::Left), 2);
+        assert_eq!(left, grid(&["O.#O.", ".....", "O..#.", "OO..."]));
+
+        let mut right = start.clone();
+        assert_eq!(slide(&mut right, Dir4::Right), 3);
+        assert_eq!(right, grid(&[".O#.O", ".....", "..O#.", "...OO"]));
+
+        let mut up = start.clone();
+        assert_eq!(slide(&mut up, Dir4::Up), 3);
+        assert_eq!(up, grid(&["O.#OO", "O.O..", "...#.", "....."]));
+
+        let mut down = start.clone();
+        assert_eq!(slide(&mut down, Dir4::Down), 3);
+        assert_eq!(down, grid(&["..#..", "...O.", "O..#.", "O.O.O"]));
+
+        // Already settled grids don't change
+        for (mut settled, dir) in [(left, Dir4::Left), (down, Dir4::Down)] {
+            let before = settled.clone();
+            assert_eq!(slide(&mut settled, dir), 0);
+            assert_eq!(settled, before);
+        }
+    }
+}
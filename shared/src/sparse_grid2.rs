@@ -0,0 +1,212 @@
+use std::{cell::Cell, collections::HashMap, ops::*};
+
+use crate::{
+    igrid2::{include_in_bounds, Bounds},
+    Grid2, Pos2, ToSignedIndex, Vec2,
+};
+
+// An infinite grid that only allocates the CHUNK x CHUNK blocks that were written to, for
+// when the written cells are too spread out for IGrid2's single dense allocation
+#[derive(Debug, Clone)]
+pub struct SparseGrid2<T: Clone, const CHUNK: usize = 32> {
+    chunks: HashMap<Pos2<isize>, Grid2<T>>,
+    default: T,
+    // The bounds of the non-default cells, computed on demand and cleared by every write
+    bounds: Cell<Option<Bounds>>,
+}
+
+fn split_pos<const CHUNK: usize>(pos: Pos2<isize>) -> (Pos2<isize>, Pos2<usize>) {
+    let size = CHUNK as isize;
+    let chunk = pos.map(|c| c.div_euclid(size));
+    let local = pos.map(|c| c.rem_euclid(size) as usize);
+    (chunk, local)
+}
+
+impl<T: Clone, const CHUNK: usize> SparseGrid2<T, CHUNK> {
+    pub fn new_with_default() -> Self
+    where
+        T: Default,
+    {
+        Self::new_with(T::default())
+    }
+
+    pub fn new_with(default: T) -> Self {
+        assert!(CHUNK > 0, "Chunk size must be non-zero");
+
+        Self {
+            chunks: HashMap::new(),
+            default,
+            bounds: Cell::new(None),
+        }
+    }
+
+    pub fn default_value(&self) -> &T {
+        &self.default
+    }
+
+    pub fn chunk_size(&self) -> usize {
+        CHUNK
+    }
+
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn get(&self, coord: Pos2<impl ToSignedIndex + Copy>) -> &T {
+        let (Some(x), Some(y)) = (coord.x.to_index(), coord.y.to_index()) else {
+            return &self.default;
+        };
+
+        let (chunk, local) = split_pos::<CHUNK>(Pos2::new(x, y));
+        match self.chunks.get(&chunk) {
+            Some(grid) => &grid[local],
+            None => &self.default,
+        }
+    }
+
+    pub fn get_mut(&mut self, coord: Pos2<impl ToSignedIndex + Copy>) -> &mut T {
+        let pos = coord.map(|c| c.to_index().expect("Coordinate doesn't fit in isize"));
+        self.bounds.set(None);
+
+        let (chunk, local) = split_pos::<CHUNK>(pos);
+        let default = &self.default;
+        let grid = self
+            .chunks
+            .entry(chunk)
+            .or_insert_with(|| Grid2::new_with(CHUNK, CHUNK, default.clone()));
+        &mut grid[local]
+    }
+
+    // Unlike writing through `get_mut`, storing the default value never allocates a chunk
+    pub fn set(&mut self, coord: Pos2<impl ToSignedIndex + Copy>, value: T)
+    where
+        T: PartialEq,
+    {
+        if value != self.default {
+            *self.get_mut(coord) = value;
+            return;
+        }
+
+        let (Some(x), Some(y)) = (coord.x.to_index(), coord.y.to_index()) else {
+            return;
+        };
+        let (chunk, local) = split_pos::<CHUNK>(Pos2::new(x, y));
+        if let Some(grid) = self.chunks.get_mut(&chunk) {
+            grid[local] = value;
+            self.bounds.set(None);
+        }
+    }
+
+    // Inclusive bounding box of all cells that currently hold a non-default value
+    pub fn bounds(&self) -> Option<(Pos2<isize>, Pos2<isize>)>
+    where
+        T: PartialEq,
+    {
+        if let Some(bounds) = self.bounds.get() {
+            return bounds;
+        }
+
+        let bounds = self
+            .iter()
+            .filter(|(_, value)| **value != self.default)
+            .map(|(pos, _)| pos)
+            .fold(None, include_in_bounds);
+        self.bounds.set(Some(bounds));
+        bounds
+    }
+
+    // Iterates the allocated chunks in no particular order, along with the position of each
+    // chunk's (0, 0) cell
+    pub fn iter_chunks(&self) -> impl '_ + Iterator<Item = (Pos2<isize>, &Grid2<T>)> {
+        self.chunks
+            .iter()
+            .map(|(chunk, grid)| (*chunk * CHUNK as isize, grid))
+    }
+
+    // Iterates every cell of every allocated chunk, chunk by chunk
+    pub fn iter(&self) -> impl '_ + Iterator<Item = (Pos2<isize>, &T)> {
+        self.iter_chunks().flat_map(|(origin, grid)| {
+            (0..CHUNK).flat_map(move |y| {
+                (0..CHUNK).map(move |x| {
+                    let local = Pos2::new(x, y);
+                    let offset = Vec2::new(x as isize, y as isize);
+                    (origin + offset, &grid[local])
+                })
+            })
+        })
+    }
+}
+
+impl<T: Clone, I: ToSignedIndex + std::fmt::Debug + Copy + Default, const CHUNK: usize>
+    Index<Pos2<I>> for SparseGrid2<T, CHUNK>
+{
+    type Output = T;
+
+    fn index(&self, index: Pos2<I>) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+impl<T: Clone, I: ToSignedIndex + std::fmt::Debug + Copy + Default, const CHUNK: usize>
+    IndexMut<Pos2<I>> for SparseGrid2<T, CHUNK>
+{
+    fn index_mut(&mut self, index: Pos2<I>) -> &mut Self::Output {
+        self.get_mut(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_grid2() {
+        let mut grid = SparseGrid2::<u8, 16>::new_with_default();
+        assert_eq!(grid[Pos2::new(5, 5)], 0);
+        assert_eq!(grid.chunk_count(), 0);
+
+        grid[Pos2::new(-1, -1)] = 1;
+        grid[Pos2::new(5_000_000i64, -7_000_000)] = 2;
+        grid[Pos2::new(0, 0)] = 3;
+
+        assert_eq!(grid[Pos2::new(-1, -1)], 1);
+        assert_eq!(grid[Pos2::new(5_000_000i64, -7_000_000)], 2);
+        assert_eq!(grid[Pos2::new(0, 0)], 3);
+        assert_eq!(grid[Pos2::new(-16, 0)], 0);
+        assert_eq!(grid.chunk_count(), 3);
+
+        assert_eq!(
+            grid.bounds(),
+            Some((Pos2::new(-1, -7_000_000), Pos2::new(5_000_000, 0)))
+        );
+
+        let mut written = grid.iter().filter(|(_, &v)| v != 0).collect::<Vec<_>>();
+        written.sort_by_key(|(_, &v)| v);
+        assert_eq!(
+            written,
+            vec![
+                (Pos2::new(-1, -1), &1),
+                (Pos2::new(5_000_000, -7_000_000), &2),
+                (Pos2::new(0, 0), &3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sparse_grid2_bounds_ignore_default() {
+        let mut grid = SparseGrid2::<u8, 4>::new_with_default();
+        grid.set(Pos2::new(100, 100), 0);
+        assert_eq!(grid.chunk_count(), 0);
+        assert_eq!(grid.bounds(), None);
+
+        grid.set(Pos2::new(1, 2), 5);
+        grid.set(Pos2::new(-3, 7), 6);
+        let _ = &mut grid[Pos2::new(50, -50)];
+        assert_eq!(grid.bounds(), Some((Pos2::new(-3, 2), Pos2::new(1, 7))));
+
+        grid.set(Pos2::new(-3, 7), 0);
+        assert_eq!(grid.bounds(), Some((Pos2::new(1, 2), Pos2::new(1, 2))));
+        grid.set(Pos2::new(1, 2), 0);
+        assert_eq!(grid.bounds(), None);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{Dir4, Pos2, Vec2};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Winding {
+    Clockwise,
+    CounterClockwise,
+}
+
+// A square spiral around the origin, like an Ulam spiral. Index 0 sits at the origin, index 1
+// one step towards `first`, and the spiral then keeps turning the same way with runs of
+// 1, 1, 2, 2, 3, 3, ... steps. Converting in either direction is O(1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UlamSpiral {
+    first: Dir4,
+    winding: Winding,
+}
+
+impl Default for UlamSpiral {
+    // Right first, then up on screen
+    fn default() -> Self {
+        Self::new(Dir4::Right, Winding::CounterClockwise)
+    }
+}
+
+impl UlamSpiral {
+    pub fn new(first: Dir4, winding: Winding) -> Self {
+        Self { first, winding }
+    }
+
+    // Grid space axes of the canonical frame, where the spiral starts along +a and first
+    // turns towards +b
+    fn axes(&self) -> (Vec2<i64>, Vec2<i64>) {
+        let side = match self.winding {
+            Winding::Clockwise => self.first.turn_right(),
+            Winding::CounterClockwise => self.first.turn_left(),
+        };
+        (self.first.to_vec(), side.to_vec())
+    }
+
+    pub fn pos(&self, index: u64) -> Pos2<i64> {
+        if index == 0 {
+            return Pos2::zero();
+        }
+
+        // Ring k holds the indices after (2k - 1)^2 - 1 up to (2k + 1)^2 - 1
+        let k = (index.isqrt() as i64 + 1) / 2;
+        let t = (index - ((2 * k - 1) * (2 * k - 1) - 1) as u64) as i64;
+        let side = 2 * k;
+
+        let (a, b) = match (t - 1) / side {
+            0 => (k, t - k),
+            1 => (k - (t - side), k),
+            2 => (-k, k - (t - 2 * side)),
+            _ => (-k + (t - 3 * side), -k),
+        };
+
+        let (along, across) = self.axes();
+        Pos2::zero() + along * a + across * b
+    }
+
+    pub fn index(&self, pos: Pos2<i64>) -> u64 {
+        let (along, across) = self.axes();
+        let offset = pos.to_vec();
+        let (a, b) = (offset.dot(along), offset.dot(across));
+
+        let k = a.abs().max(b.abs());
+        if k == 0 {
+            return 0;
+        }
+
+        let side = 2 * k;
+        let t = if a == k && b > -k {
+            b + k
+        } else if b == k {
+            side + (k - a)
+        } else if a == -k {
+            2 * side + (k - b)
+        } else {
+            3 * side + (a + k)
+        };
+
+        ((2 * k - 1) * (2 * k - 1) - 1 + t) as u64
+    }
+
+    // Endless, starting from the origin
+    pub fn iter(&self) -> impl Iterator<Item = Pos2<i64>> {
+        let spiral = *self;
+        (0..).map(move |i| spiral.pos(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ulam_spiral() {
+        let spiral = UlamSpiral::default();
+        let start = spiral.iter().take(10).collect::<Vec<_>>();
+        assert_eq!(
+            start,
+            [
+                (0, 0),
+                (1, 0),
+                (1, -1),
+                (0, -1),
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+                (2, 1)
+            ]
+            .map(|(x, y)| Pos2::new(x, y))
+        );
+
+        assert_eq!(spiral.pos(1023).manhattan_distance(Pos2::zero()), 31);
+        assert_eq!(spiral.pos(11).manhattan_distance(Pos2::zero()), 3);
+
+        let other = UlamSpiral::new(Dir4::Up, Winding::Clockwise);
+        assert_eq!(other.pos(1), Pos2::new(0, -1));
+        assert_eq!(other.pos(2), Pos2::new(1, -1));
+
+        for spiral in [spiral, other] {
+            for (i, pos) in spiral.iter().take(500).enumerate() {
+                assert_eq!(spiral.index(pos), i as u64);
+            }
+        }
+    }
+}
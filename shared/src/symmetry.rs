@@ -0,0 +1,190 @@
+use crate::{Grid2, Pos2};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reflection<P> {
+    // Number of items (rows, columns, elements) before the mirror line
+    pub offset: usize,
+    // Pairs of mirrored positions that didn't match
+    pub mismatches: Vec<(P, P)>,
+}
+
+impl<P> Reflection<P> {
+    pub fn mismatch_count(&self) -> usize {
+        self.mismatches.len()
+    }
+
+    pub fn is_exact(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+fn find_reflections_with<P>(
+    len: usize,
+    max_mismatches: usize,
+    mut push_mismatches: impl FnMut(usize, usize, &mut Vec<(P, P)>),
+) -> Vec<Reflection<P>> {
+    let mut reflections = Vec::new();
+
+    for offset in 1..len {
+        let mut mismatches = Vec::new();
+
+        // Walk outwards from the mirror line until either side runs out
+        for (a, b) in (0..offset).rev().zip(offset..len) {
+            push_mismatches(a, b, &mut mismatches);
+            if mismatches.len() > max_mismatches {
+                break;
+            }
+        }
+
+        if mismatches.len() <= max_mismatches {
+            reflections.push(Reflection { offset, mismatches });
+        }
+    }
+
+    reflections
+}
+
+pub fn find_seq_reflections<T: PartialEq>(
+    items: &[T],
+    max_mismatches: usize,
+) -> Vec<Reflection<usize>> {
+    find_seq_reflections_by(items, max_mismatches, |a, b| a == b)
+}
+
+pub fn find_seq_reflections_by<T>(
+    items: &[T],
+    max_mismatches: usize,
+    eq: impl Fn(&T, &T) -> bool,
+) -> Vec<Reflection<usize>> {
+    find_reflections_with(items.len(), max_mismatches, |a, b, mismatches| {
+        if !eq(&items[a], &items[b]) {
+            mismatches.push((a, b));
+        }
+    })
+}
+
+impl<T: PartialEq> Grid2<T> {
+    // Mirror lines running horizontally, i.e. between two rows
+    pub fn find_horizontal_reflections(
+        &self,
+        max_mismatches: usize,
+    ) -> Vec<Reflection<Pos2<usize>>> {
+        find_reflections_with(self.height(), max_mismatches, |y1, y2, mismatches| {
+            for x in 0..self.width() {
+                let (a, b) = (Pos2::new(x, y1), Pos2::new(x, y2));
+                if self[a] != self[b] {
+                    mismatches.push((a, b));
+                }
+            }
+        })
+    }
+
+    // Mirror lines running vertically, i.e. between two columns
+    pub fn find_vertical_reflections(&self, max_mismatches: usize) -> Vec<Reflection<Pos2<usize>>> {
+        find_reflections_with(self.width(), max_mismatches, |x1, x2, mismatches| {
+            for y in 0..self.height() {
+                let (a, b) = (Pos2::new(x1, y), Pos2::new(x2, y));
+                if self[a] != self[b] {
+                    mismatches.push((a, b));
+                }
+            }
+        })
+    }
+
+    // Compares the grid against itself rotated clockwise by the given number of quarter turns.
+    // Returns None if the rotation can't map the grid onto itself (non-square with an odd turn count),
+    // or if there are more mismatches than allowed. Each mismatching pair is only reported once.
+    pub fn rotational_symmetry(
+        &self,
+        quarter_turns: usize,
+        max_mismatches: usize,
+    ) -> Option<Vec<(Pos2<usize>, Pos2<usize>)>> {
+        let quarter_turns = quarter_turns % 4;
+        if quarter_turns % 2 == 1 && self.width() != self.height() {
+            return None;
+        }
+
+        let (w, h) = (self.width(), self.height());
+        let rotate = |pos: Pos2<usize>| match quarter_turns {
+            0 => pos,
+            1 => Pos2::new(h - 1 - pos.y, pos.x),
+            2 => Pos2::new(w - 1 - pos.x, h - 1 - pos.y),
+            _ => Pos2::new(pos.y, w - 1 - pos.x),
+        };
+
+        let mut mismatches = Vec::new();
+        for y in 0..h {
+            for x in 0..w {
+                let pos = Pos2::new(x, y);
+                let rotated = rotate(pos);
+                if self[pos] == self[rotated] {
+                    continue;
+                }
+
+                // A half turn pairs cells up symmetrically, so skip the second half of each pair
+                if quarter_turns == 2 && (rotated.y, rotated.x) < (y, x) {
+                    continue;
+                }
+
+                mismatches.push((pos, rotated));
+                if mismatches.len() > max_mismatches {
+                    return None;
+                }
+            }
+        }
+
+        Some(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(s: &str) -> Grid2<char> {
+        Grid2::from_rows(s.lines().map(|l| l.chars())).unwrap()
+    }
+
+    #[test]
+    fn test_grid_reflections() {
+        let a = grid("#.##..##.\n..#.##.#.\n##......#\n##......#\n..#.##.#.\n..##..##.\n#.#.##.#.");
+        let b = grid("#...##..#\n#....#..#\n..##..###\n#####.##.\n#####.##.\n..##..###\n#....#..#");
+
+        let a_cols = a.find_vertical_reflections(0);
+        assert_eq!(a_cols.len(), 1);
+        assert_eq!(a_cols[0].offset, 5);
+        assert!(a.find_horizontal_reflections(0).is_empty());
+
+        let b_rows = b.find_horizontal_reflections(0);
+        assert_eq!(b_rows.len(), 1);
+        assert_eq!(b_rows[0].offset, 4);
+
+        let a_smudged = a
+            .find_horizontal_reflections(1)
+            .into_iter()
+            .filter(|r| r.mismatch_count() == 1)
+            .collect::<Vec<_>>();
+        assert_eq!(a_smudged.len(), 1);
+        assert_eq!(a_smudged[0].offset, 3);
+        assert_eq!(
+            a_smudged[0].mismatches,
+            vec![(Pos2::new(0, 0), Pos2::new(0, 5))]
+        );
+    }
+
+    #[test]
+    fn test_seq_and_rotation() {
+        let reflections = find_seq_reflections(&[1, 2, 2, 1, 5], 0);
+        assert_eq!(reflections.len(), 1);
+        assert_eq!(reflections[0].offset, 2);
+
+        let square = grid("#..\n.#.\n..#");
+        assert_eq!(square.rotational_symmetry(2, 0), Some(vec![]));
+        assert_eq!(square.rotational_symmetry(1, 0), None);
+        assert_eq!(square.rotational_symmetry(1, 4).map(|m| m.len()), Some(4));
+
+        let wide = grid("#.#\n#.#");
+        assert_eq!(wide.rotational_symmetry(1, 10), None);
+        assert_eq!(wide.rotational_symmetry(2, 0), Some(vec![]));
+    }
+}